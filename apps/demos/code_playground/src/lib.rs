@@ -1,5 +1,7 @@
 use components::{docs::render_snippet, ThemeSwitch};
-use designsystem::{install_defaults, IconName, ThemeRegistry};
+use designsystem::{
+    install_defaults, IconName, SpacingToken, TextRole, ThemeRegistry, TokenStyled as _,
+};
 use gpui::{
     prelude::*, px, size, App, Application, Bounds, Context, Window, WindowBounds, WindowOptions,
 };
@@ -24,7 +26,10 @@ pub fn run() {
         registry.install(cx);
 
         let store = ConfigStore::default();
-        bootstrap(cx, &store).expect("workspace configuration");
+        let loaded = bootstrap(cx, &store);
+        for warning in loaded.warnings() {
+            eprintln!("{warning}");
+        }
 
         launch(cx, registry.clone());
 
@@ -232,19 +237,20 @@ if !lsp_online {
 
 impl gpui::Render for CodePlaygroundApp {
     fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        self.theme_registry.scope_window(window, cx);
         v_flex()
             .size_full()
             .bg(cx.theme().background)
-            .p_6()
-            .gap_4()
+            .p_token(SpacingToken::XL, cx)
+            .gap_token(SpacingToken::LG, cx)
             .child(
                 h_flex()
                     .justify_between()
                     .items_center()
                     .child(
                         v_flex()
-                            .gap_1()
-                            .child(Text::new("Code Playground").size(22.0).font_weight_bold())
+                            .gap_token(SpacingToken::XS, cx)
+                            .child(Text::new("Code Playground").text_style(TextRole::Headline, cx))
                             .child(Text::new(
                                 "Experiment with gpui's code editor, mocked file explorer, and diagnostics hooked to rust-analyzer.",
                             )),
@@ -1,5 +1,7 @@
 use components::{docs::render_snippet, DashboardCard, KpiGrid, KpiMetric, ThemeSwitch};
-use designsystem::{install_defaults, IconName, ThemeRegistry};
+use designsystem::{
+    install_defaults, IconName, SpacingToken, TextRole, ThemeRegistry, TokenStyled as _,
+};
 use gpui::{
     div, prelude::*, px, size, App, Application, Bounds, Context, Window, WindowBounds,
     WindowOptions,
@@ -27,7 +29,10 @@ pub fn run() {
         registry.install(cx);
 
         let store = ConfigStore::default();
-        bootstrap(cx, &store).expect("workspace configuration");
+        let loaded = bootstrap(cx, &store);
+        for warning in loaded.warnings() {
+            eprintln!("{warning}");
+        }
 
         launch(cx, registry.clone());
 
@@ -308,19 +313,20 @@ tokio::spawn(async move { refresh_kpis().await });"#;
 
 impl gpui::Render for DashboardDemoApp {
     fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        self.theme_registry.scope_window(window, cx);
         v_flex()
             .size_full()
             .bg(cx.theme().background)
-            .p_6()
-            .gap_5()
+            .p_token(SpacingToken::XL, cx)
+            .gap_token(SpacingToken::XL, cx)
             .child(
                 h_flex()
                     .justify_between()
                     .items_center()
                     .child(
                         v_flex()
-                            .gap_1()
-                            .child(Text::new("Operations command center").size(22.0).font_weight_bold())
+                            .gap_token(SpacingToken::XS, cx)
+                            .child(Text::new("Operations command center").text_style(TextRole::Headline, cx))
                             .child(Text::new(
                                 "Purpose-built dashboard for SRE incident response – KPIs, charts, alerts, and triage tools share the same design tokens as the workbench.",
                             )),
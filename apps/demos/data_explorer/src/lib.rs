@@ -5,7 +5,9 @@ use data::{
     generate_transactions, Transaction, TransactionCategory, TransactionStatus,
     VirtualListBenchmark,
 };
-use designsystem::{install_defaults, IconName, ThemeRegistry};
+use designsystem::{
+    install_defaults, IconName, SpacingToken, TextRole, ThemeRegistry, TokenStyled as _,
+};
 use gpui::{
     prelude::*, px, size, App, Application, Bounds, Context, Window, WindowBounds, WindowOptions,
 };
@@ -31,7 +33,10 @@ pub fn run() {
         registry.install(cx);
 
         let store = ConfigStore::default();
-        let _config = bootstrap(cx, &store).expect("workspace configuration");
+        let loaded = bootstrap(cx, &store);
+        for warning in loaded.warnings() {
+            eprintln!("{warning}");
+        }
 
         launch(cx, registry.clone());
 
@@ -542,22 +547,23 @@ if rows.len() > 200_000 {
 
 impl gpui::Render for DataExplorerApp {
     fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        self.theme_registry.scope_window(window, cx);
         let theme_switch =
             ThemeSwitch::new("explorer-theme", self.theme_registry.clone()).label("Theme");
 
         v_flex()
             .size_full()
             .bg(cx.theme().background)
-            .p_6()
-            .gap_5()
+            .p_token(SpacingToken::XL, cx)
+            .gap_token(SpacingToken::XL, cx)
             .child(
                 h_flex()
                     .justify_between()
                     .items_center()
                     .child(
                         v_flex()
-                            .gap_1()
-                            .child(Text::new("Streaming Data Explorer").size(22.0).font_weight_bold())
+                            .gap_token(SpacingToken::XS, cx)
+                            .child(Text::new("Streaming Data Explorer").text_style(TextRole::Headline, cx))
                             .child(Text::new(
                                 "Benchmark million-row payloads with pagination controls, tunable virtualization buffers, and instrumented performance metrics.",
                             )),
@@ -1,5 +1,7 @@
 use components::{docs::render_snippet, ThemeSwitch};
-use designsystem::{install_defaults, IconName, ThemeRegistry};
+use designsystem::{
+    install_defaults, IconName, SpacingToken, TextRole, ThemeRegistry, TokenStyled as _,
+};
 use gpui::{
     div, prelude::*, px, size, App, Application, Bounds, Context, Window, WindowBounds,
     WindowOptions,
@@ -26,7 +28,11 @@ pub fn run() {
         registry.install(cx);
 
         let store = ConfigStore::default();
-        let config = bootstrap(cx, &store).expect("workspace configuration");
+        let loaded = bootstrap(cx, &store);
+        for warning in loaded.warnings() {
+            eprintln!("{warning}");
+        }
+        let config = loaded.config;
 
         launch(cx, registry.clone(), store.clone(), config.clone());
 
@@ -47,7 +53,21 @@ pub fn launch(cx: &mut App, registry: ThemeRegistry, store: ConfigStore, config:
         },
         move |window, cx| {
             window.set_title("Markdown & Notes Workspace");
-            cx.new(|_| MarkdownNotesApp::new(registry.clone(), store.clone(), config.clone()))
+            let notes =
+                cx.new(|_| MarkdownNotesApp::new(registry.clone(), store.clone(), config.clone()));
+            let this = notes.downgrade();
+            let watched = store.watch(
+                cx,
+                move |config, cx| {
+                    let _ = this.update(cx, |app, cx| app.apply_external_config(config, cx));
+                },
+                |err, _| eprintln!("failed to reload notes config: {err}"),
+            );
+            match watched {
+                Ok(watcher) => watcher.detach(),
+                Err(err) => eprintln!("failed to watch notes config: {err}"),
+            }
+            notes
         },
     )
     .expect("markdown notes window");
@@ -91,13 +111,30 @@ impl MarkdownNotesApp {
         self.buffer = contents;
         self.workspace_config.push_recent(name.clone());
         self.recent_documents = self.workspace_config.recent_workspaces.clone();
-        if let Err(err) = self.config_store.save(&self.workspace_config) {
-            eprintln!("failed to persist notes config: {err}");
-        }
+        // Merge into the stored config so settings other windows and
+        // processes saved since startup survive.
+        let recent = name.clone();
+        let saved = self
+            .config_store
+            .update_in_background(cx, move |config| config.push_recent(recent));
+        cx.spawn(async move |this, cx| match saved.await {
+            Ok(config) => {
+                let _ = this.update(cx, |notes, cx| notes.apply_external_config(&config, cx));
+            }
+            Err(err) => eprintln!("failed to persist notes config: {err}"),
+        })
+        .detach();
         self.status = Some(format!("Loaded {name}"));
         cx.notify();
     }
 
+    /// Picks up recent documents another process saved.
+    fn apply_external_config(&mut self, config: &WorkspaceConfig, cx: &mut Context<Self>) {
+        self.workspace_config = config.clone();
+        self.recent_documents = config.recent_workspaces.clone();
+        cx.notify();
+    }
+
     fn simulate_drop(&mut self, cx: &mut Context<Self>) {
         let name = format!("meeting-notes-{}.md", self.synthetic_counter);
         self.synthetic_counter += 1;
@@ -211,7 +248,7 @@ impl MarkdownNotesApp {
         let usage = r#"let mut notes = MarkdownNotesApp::new(theme_registry.clone(), store.clone(), config.clone());
 notes.load_document("retro.md".into(), contents, cx);"#;
 
-        let integration = r#"platform::bootstrap(cx, &store)?;
+        let integration = r#"let config = platform::bootstrap(cx, &store).config;
 cx.open_window(WindowOptions::default(), move |window, cx| {
     window.set_title("Notes");
     cx.new(|_| MarkdownNotesApp::new(theme.clone(), store.clone(), config.clone()))
@@ -259,6 +296,7 @@ if note.bytes().len() > 500_000 {
 
 impl gpui::Render for MarkdownNotesApp {
     fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        self.theme_registry.scope_window(window, cx);
         let status_banner = self.status.clone().map(|message| {
             Alert::new("notes-status")
                 .variant(AlertVariant::Success)
@@ -268,16 +306,16 @@ impl gpui::Render for MarkdownNotesApp {
         v_flex()
             .size_full()
             .bg(cx.theme().background)
-            .p_6()
-            .gap_4()
+            .p_token(SpacingToken::XL, cx)
+            .gap_token(SpacingToken::LG, cx)
             .child(
                 h_flex()
                     .justify_between()
                     .items_center()
                     .child(
                         v_flex()
-                            .gap_1()
-                            .child(Text::new("Markdown workspace").size(22.0).font_weight_bold())
+                            .gap_token(SpacingToken::XS, cx)
+                            .child(Text::new("Markdown workspace").text_style(TextRole::Headline, cx))
                             .child(Text::new(
                                 "Capture meeting notes, architecture decisions, and runbooks side-by-side with live previews.",
                             )),
@@ -12,7 +12,7 @@ pub fn run() {
 #[cfg(feature = "webview")]
 pub mod app {
     use components::{docs::render_snippet, ThemeSwitch};
-    use designsystem::{install_defaults, ThemeRegistry};
+    use designsystem::{install_defaults, SpacingToken, TextRole, ThemeRegistry, TokenStyled as _};
     use gpui::{
         prelude::*, px, size, App, Application, Bounds, Context, Window, WindowBounds,
         WindowOptions,
@@ -37,7 +37,10 @@ pub mod app {
             registry.install(cx);
 
             let store = ConfigStore::default();
-            bootstrap(cx, &store).expect("workspace configuration");
+            let loaded = bootstrap(cx, &store);
+            for warning in loaded.warnings() {
+                eprintln!("{warning}");
+            }
 
             launch(cx, registry.clone());
 
@@ -170,14 +173,14 @@ if url.scheme() != "file" {
                 })
         }
 
-        fn render_toolbar(&self) -> impl IntoElement {
+        fn render_toolbar(&self, cx: &App) -> impl IntoElement {
             h_flex()
                 .justify_between()
                 .items_center()
                 .child(
                     v_flex()
-                        .gap_1()
-                        .child(Text::new("Embedded documentation").size(22.0).font_weight_bold())
+                        .gap_token(SpacingToken::XS, cx)
+                        .child(Text::new("Embedded documentation").text_style(TextRole::Headline, cx))
                         .child(Text::new(
                             "Load local HTML alongside GPUI surfaces to document deployment runbooks and onboarding flows.",
                         )),
@@ -188,12 +191,13 @@ if url.scheme() != "file" {
 
     impl gpui::Render for WebviewDemoApp {
         fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+            self.theme_registry.scope_window(window, cx);
             v_flex()
                 .size_full()
                 .bg(cx.theme().background)
-                .p_6()
-                .gap_4()
-                .child(self.render_toolbar())
+                .p_token(SpacingToken::XL, cx)
+                .gap_token(SpacingToken::LG, cx)
+                .child(self.render_toolbar(cx))
                 .child(
                     h_resizable("webview-split")
                         .panel(resizable_panel("content", 0.65).child(self.render_content()))
@@ -3,10 +3,13 @@ use std::time::Duration;
 
 use clap::Parser;
 use components::{docs::render_snippet, DockLayoutPanel, ThemeSwitch};
-use designsystem::{install_defaults, IconLoader, IconName, ThemeRegistry, ThemeVariant};
+use designsystem::{
+    audit_contrast, ActiveIconLabels as _, DesignTokens, IconLoader, IconName, IconPackSource,
+    ThemeRegistry, ThemeVariant,
+};
 use gpui::{
-    div, prelude::*, px, size, AnyElement, App, Application, Bounds, Context, SharedString, Timer,
-    Window, WindowBounds, WindowOptions,
+    div, prelude::*, px, size, svg, AnyElement, App, Application, Bounds, Context, Entity,
+    SharedString, Subscription, Timer, Window, WindowBounds, WindowOptions,
 };
 use gpui::platform::keystroke::Keystroke;
 use gpui_component::{
@@ -14,6 +17,7 @@ use gpui_component::{
     button::{Button, ButtonVariants as _},
     group_box::GroupBox,
     icon::Icon,
+    input::{Input, InputEvent, InputState},
     kbd::Kbd,
     resizable::{h_resizable, resizable_panel},
     styled::{h_flex, v_flex, StyledExt as _},
@@ -171,13 +175,33 @@ fn main() {
     let cli = GalleryCli::parse();
     let launch_targets = cli.open;
 
-    let app = install_defaults(Application::new());
+    // Serves the embedded icons plus any packs in `GPUI_ICON_PACKS`, one
+    // subdirectory per pack.
+    let icon_packs = IconPackSource::new();
+    if let Some(packs_dir) = std::env::var_os("GPUI_ICON_PACKS") {
+        if let Err(err) = icon_packs.load_dir(packs_dir) {
+            eprintln!("failed to load icon packs: {err}");
+        }
+    }
+    let app = Application::new().with_assets(icon_packs.clone());
     app.run(move |cx| {
         let registry = ThemeRegistry::new();
         registry.install(cx);
 
+        let watched = registry.load_and_watch_env(cx, |err, _| {
+            eprintln!("theme reload failed: {err}");
+        });
+        match watched {
+            Ok(Some(watcher)) => watcher.detach(),
+            Ok(None) => {}
+            Err(err) => eprintln!("failed to load custom themes: {err}"),
+        }
+
         let store = ConfigStore::default();
-        bootstrap(cx, &store).expect("workspace configuration");
+        let loaded = bootstrap(cx, &store);
+        for warning in loaded.warnings() {
+            eprintln!("{warning}");
+        }
 
         let localization = seed_localization();
         let command_bus = CommandBus::new();
@@ -196,12 +220,15 @@ fn main() {
             move |window, cx| {
                 window.set_title("GPUI Component Gallery");
                 let pending_launches = pending_launches.clone();
-                cx.new(move |_| {
+                cx.new(move |cx| {
                     GalleryApp::new(
                         registry.clone(),
                         localization.clone(),
                         command_bus.clone(),
+                        icon_packs.clone(),
                         pending_launches.clone(),
+                        window,
+                        cx,
                     )
                 })
             },
@@ -277,6 +304,7 @@ fn seed_localization() -> LocalizationRegistry {
             ),
         ],
     );
+    registry.register_icon_labels();
     registry
 }
 
@@ -363,6 +391,9 @@ struct GalleryApp {
     active_category: usize,
     palette_overlay: bool,
     icon_set: usize,
+    icon_packs: IconPackSource,
+    icon_query: Entity<InputState>,
+    _icon_query_subscription: Subscription,
     layout_epoch: u64,
     theme_preview: ThemeVariant,
     pending_launches: Vec<GalleryLaunchTarget>,
@@ -401,10 +432,19 @@ impl GalleryApp {
         theme_registry: ThemeRegistry,
         localization: LocalizationRegistry,
         command_bus: CommandBus<GalleryCommand>,
+        icon_packs: IconPackSource,
         pending_launches: Vec<GalleryLaunchTarget>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
     ) -> Self {
         let receiver = command_bus.subscribe();
-        Self {
+        let icon_query = cx.new(|cx| InputState::new(window, cx).placeholder("Search icons"));
+        // Re-render the results as the query changes.
+        let icon_query_subscription =
+            cx.subscribe_in(&icon_query, window, |_, _, _: &InputEvent, _, cx| {
+                cx.notify();
+            });
+        let app = Self {
             theme_preview: theme_registry.active(),
             theme_registry,
             localization,
@@ -415,10 +455,15 @@ impl GalleryApp {
             active_category: 0,
             palette_overlay: false,
             icon_set: 0,
+            icon_packs,
+            icon_query,
+            _icon_query_subscription: icon_query_subscription,
             layout_epoch: 0,
             pending_launches,
             command_palette_open: false,
-        }
+        };
+        app.localization.icon_labels(&app.locale).install(cx);
+        app
     }
 
     fn t(&self, key: &str) -> SharedString {
@@ -465,8 +510,7 @@ impl GalleryApp {
                 cx.notify();
             }
             GalleryLaunchTarget::Theme(selector) => {
-                self.apply_theme(selector.variant(), cx);
-                window.refresh();
+                self.apply_theme(selector.variant(), window, cx);
             }
             GalleryLaunchTarget::PaletteOverlay => {
                 self.palette_overlay = true;
@@ -481,6 +525,7 @@ impl GalleryApp {
             }
             GalleryLaunchTarget::Locale(locale) => {
                 self.locale = locale;
+                self.localization.icon_labels(&self.locale).install(cx);
                 cx.notify();
             }
         }
@@ -509,8 +554,8 @@ impl GalleryApp {
         }
     }
 
-    fn apply_theme(&mut self, variant: ThemeVariant, cx: &mut Context<Self>) {
-        self.theme_registry.apply(variant, cx);
+    fn apply_theme(&mut self, variant: ThemeVariant, window: &mut Window, cx: &mut Context<Self>) {
+        self.theme_registry.set_window_theme(window, Some(variant));
         self.theme_preview = variant;
         cx.notify();
     }
@@ -753,8 +798,7 @@ impl GalleryApp {
                             .selected_index(active_ix)
                             .on_click(cx.listener(|this, index, window, cx| {
                                 if let Some((variant, _, _)) = THEME_VARIANTS.get(*index) {
-                                    this.apply_theme(*variant, cx);
-                                    window.refresh();
+                                    this.apply_theme(*variant, window, cx);
                                 }
                             }))
                             .children(THEME_VARIANTS.iter().enumerate().map(
@@ -806,6 +850,16 @@ impl GalleryApp {
             ("Success", cx.theme().success, palette.success),
             ("Warning", cx.theme().warning, palette.warning),
             ("Danger", cx.theme().danger, palette.danger),
+            ("Selection", cx.theme().selection, palette.selection),
+            ("Ring", cx.theme().ring, palette.ring),
+            ("Input", cx.theme().input, palette.input_border),
+            ("Tab Bar", cx.theme().tab_bar, palette.tab_bar),
+            ("Sidebar", cx.theme().sidebar, palette.sidebar),
+            ("Chart 1", cx.theme().chart_1, palette.chart_1),
+            ("Chart 2", cx.theme().chart_2, palette.chart_2),
+            ("Chart 3", cx.theme().chart_3, palette.chart_3),
+            ("Chart 4", cx.theme().chart_4, palette.chart_4),
+            ("Chart 5", cx.theme().chart_5, palette.chart_5),
         ];
 
         GroupBox::new()
@@ -830,12 +884,51 @@ impl GalleryApp {
                             )
                             .child(Text::new(label))
                             .child(
-                                Text::new(hex)
+                                Text::new(hex.to_hex())
                                     .text_color(cx.theme().muted_foreground)
                                     .size(12.0),
                             )
                     })),
             )
+            .child(self.render_contrast_audit(&definition.tokens, cx))
+    }
+
+    fn render_contrast_audit(
+        &self,
+        tokens: &DesignTokens,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        let report = audit_contrast(tokens);
+
+        v_flex()
+            .gap_1()
+            .pt_3()
+            .child(Text::new("Contrast audit").font_weight_semibold())
+            .children(report.checks.into_iter().map(|check| {
+                let verdict = if check.aaa {
+                    "AAA"
+                } else if check.aa {
+                    "AA"
+                } else {
+                    "Fail"
+                };
+                h_flex()
+                    .gap_2()
+                    .child(div().w(px(200.0)).child(Text::new(format!(
+                        "{} on {}",
+                        check.foreground, check.background
+                    ))))
+                    .child(
+                        div()
+                            .w(px(72.0))
+                            .child(Text::new(format!("{:.2}:1", check.ratio))),
+                    )
+                    .child(Text::new(verdict).text_color(if check.aa {
+                        cx.theme().success
+                    } else {
+                        cx.theme().danger
+                    }))
+            }))
     }
 
     fn render_launcher(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
@@ -892,16 +985,14 @@ impl GalleryApp {
                         Button::new("quick-dark")
                             .label("Dark theme")
                             .on_click(cx.listener(|this, _, window, cx| {
-                                this.apply_theme(ThemeVariant::Dark, cx);
-                                window.refresh();
+                                this.apply_theme(ThemeVariant::Dark, window, cx);
                             })),
                     )
                     .child(
                         Button::new("quick-contrast")
                             .label("High contrast")
                             .on_click(cx.listener(|this, _, window, cx| {
-                                this.apply_theme(ThemeVariant::HighContrast, cx);
-                                window.refresh();
+                                this.apply_theme(ThemeVariant::HighContrast, window, cx);
                             })),
                     ),
             )
@@ -1210,6 +1301,9 @@ button.build(window, cx);"#,
         let heading = self.t("icons.heading");
         let docs = self.t("icons.docs");
         let active = ICON_SETS.get(self.icon_set).unwrap_or(&ICON_SETS[0]);
+        let query = self.icon_query.read(cx).value();
+        let results = IconLoader::search(&query);
+        let packs = self.icon_packs.packs();
 
         v_flex()
             .gap_4()
@@ -1257,9 +1351,61 @@ button.build(window, cx);"#,
                                         .selected(ix == self.icon_set)
                                         .id(format!("icon-tab-{ix}"))
                                 })),
-                        ),
+                        )
+                        .child(Input::new(&self.icon_query))
+                        .child(if results.is_empty() {
+                            Text::new(format!("No icons match \u{201c}{query}\u{201d}."))
+                                .text_color(cx.theme().muted_foreground)
+                                .into_any_element()
+                        } else {
+                            h_flex()
+                                .gap_3()
+                                .flex_wrap()
+                                .children(results.into_iter().map(|icon| {
+                                    let metadata = icon.metadata();
+                                    v_flex()
+                                        .items_center()
+                                        .gap_1()
+                                        .child(
+                                            svg()
+                                                .path(icon.asset_path())
+                                                .size_6()
+                                                .text_color(cx.theme().foreground),
+                                        )
+                                        .child(Text::new(cx.icon_label(icon)).size(12.0))
+                                        .child(
+                                            Text::new(metadata.category)
+                                                .size(12.0)
+                                                .text_color(cx.theme().muted_foreground),
+                                        )
+                                }))
+                                .into_any_element()
+                        }),
                 ),
             )
+            .when(!packs.is_empty(), |this| {
+                this.child(
+                    GroupBox::new()
+                        .title(Text::new("Packs loaded from disk"))
+                        .child(v_flex().gap_3().children(packs.into_iter().map(|prefix| {
+                            let icons = self.icon_packs.pack_icons(&prefix);
+                            v_flex()
+                                .gap_2()
+                                .child(
+                                    Text::new(format!("{prefix} ({} icons)", icons.len()))
+                                        .text_color(cx.theme().muted_foreground),
+                                )
+                                .child(h_flex().gap_3().flex_wrap().children(icons.into_iter().map(
+                                    |path| {
+                                        svg()
+                                            .path(path)
+                                            .size_6()
+                                            .text_color(cx.theme().foreground)
+                                    },
+                                )))
+                        }))),
+                )
+            })
             .child(Text::new(docs).text_color(cx.theme().muted_foreground))
             .child(doc_section(
                 "icons-snippet",
@@ -1270,6 +1416,8 @@ button.build(window, cx);"#,
                 &[ 
                     "Bundle icons into small runtime sets so overlays only load what they need.",
                     "Normalize new packs with `cargo xtask icons --pack product <dir>` so SVG attributes match gpui expectations.",
+                    "Describe icons in a `<stem>.toml` sidecar (label, category, tags, synonyms) so `IconLoader::search` finds them by meaning.",
+                    "Ship icons without rebuilding by pointing `GPUI_ICON_PACKS` at a directory of packs; `IconPackSource` serves `<pack>/<icon>.svg` under `designsystem/icons/<pack>/`.",
                 ],
                 &[
                     "After adding SVGs, rebuild the workspace—cached icons will not update until the loader regenerates.",
@@ -1316,6 +1464,8 @@ button.build(window, cx);"#,
 
 impl gpui::Render for GalleryApp {
     fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        self.theme_registry.scope_window(window, cx);
+        self.theme_preview = self.theme_registry.window_theme(window);
         self.process_commands(cx);
         self.process_pending(window, cx);
 
@@ -1366,8 +1516,7 @@ fn action_switch_high_contrast(
     window: &mut Window,
     cx: &mut Context<GalleryApp>,
 ) {
-    app.apply_theme(ThemeVariant::HighContrast, cx);
-    window.refresh();
+    app.apply_theme(ThemeVariant::HighContrast, window, cx);
 }
 
 fn action_toggle_palette_overlay(
@@ -7,10 +7,12 @@ use components::{
     docs::render_snippet, DashboardCard, DockLayoutPanel, KpiGrid, KpiMetric, ThemeSwitch,
 };
 use data::VirtualListBenchmark;
-use designsystem::{install_defaults, IconName, ThemeRegistry, ThemeVariant};
+use designsystem::{
+    install_defaults, IconName, ThemePreference, ThemeRegistry, ThemeTransition, ThemeVariant,
+};
 use gpui::{
-    div, prelude::*, px, size, App, Application, Bounds, Context, Keystroke, SharedString, Window,
-    WindowBounds, WindowOptions,
+    div, prelude::*, px, size, App, Application, Bounds, Context, Keystroke, SharedString,
+    Subscription, Window, WindowBounds, WindowOptions,
 };
 use gpui_component::{
     accordion::Accordion,
@@ -30,8 +32,8 @@ use gpui_component::{
     ContextModal,
 };
 use platform::{
-    bootstrap, BenchmarkRunRecord, CommandBus, ConfigStore, EditorBenchmarkSummary, LayoutState,
-    LocalizationRegistry, VirtualizationBenchmarkSummary, WorkspaceConfig,
+    bootstrap, BenchmarkRunRecord, CommandBus, ConfigStore, ConfigWatcher, EditorBenchmarkSummary,
+    LayoutState, LocalizationRegistry, VirtualizationBenchmarkSummary, WorkspaceConfig,
 };
 use unic_langid::{langid, LanguageIdentifier};
 
@@ -122,8 +124,31 @@ fn main() {
         let registry = ThemeRegistry::new();
         registry.install(cx);
 
+        let watched = registry.load_and_watch_env(cx, |err, _| {
+            eprintln!("theme reload failed: {err}");
+        });
+        match watched {
+            Ok(Some(watcher)) => watcher.detach(),
+            Ok(None) => {}
+            Err(err) => eprintln!("failed to load custom themes: {err}"),
+        }
+
         let store = ConfigStore::default();
-        let config = bootstrap(cx, &store).expect("workspace configuration");
+        let loaded = bootstrap(cx, &store);
+        for warning in loaded.warnings() {
+            eprintln!("{warning}");
+        }
+        let config = loaded.config;
+        let preference = config
+            .theme_preference
+            .as_deref()
+            .and_then(|slug| registry.preference_from_str(slug).ok())
+            .unwrap_or(ThemePreference::SYSTEM);
+        registry.set_preference(preference, cx);
+        registry.set_transition(
+            ThemeTransition::fade(ThemeTransition::DEFAULT_DURATION)
+                .with_reduced_motion(config.reduce_motion),
+        );
         let localization = seed_localization();
         let command_bus = CommandBus::new();
         let open_requests = launch_targets.clone();
@@ -140,6 +165,7 @@ fn main() {
             },
             move |window, cx| {
                 window.set_title("GPUI Workbench Shell");
+                registry.follow_system_appearance(window, cx).detach();
                 let open_requests = open_requests.clone();
                 let registry_for_app = registry.clone();
                 let localization_for_app = localization.clone();
@@ -147,13 +173,14 @@ fn main() {
                 let config_for_app = config.clone();
                 let bus_for_app = command_bus.clone();
                 let bus_for_launch = command_bus.clone();
-                cx.new(move |_| {
+                cx.new(move |cx| {
                     let mut app = WorkbenchApp::new(
                         registry_for_app.clone(),
                         localization_for_app.clone(),
                         bus_for_app.clone(),
                         config_for_app.clone(),
                         store_for_app.clone(),
+                        cx,
                     );
                     for target in &open_requests {
                         match target {
@@ -256,6 +283,7 @@ fn seed_localization() -> LocalizationRegistry {
             ("toast.body", "Preferencias sincronizadas"),
         ],
     );
+    registry.register_icon_labels();
     registry
 }
 
@@ -623,6 +651,8 @@ struct WorkbenchApp {
     chart_tick: usize,
     theme_variant: ThemeVariant,
     performance: PerformanceState,
+    _theme_subscription: Subscription,
+    _config_watcher: Option<ConfigWatcher>,
 }
 
 impl WorkbenchApp {
@@ -632,9 +662,29 @@ impl WorkbenchApp {
         command_bus: CommandBus<WorkbenchCommand>,
         workspace_config: WorkspaceConfig,
         config_store: ConfigStore,
+        cx: &mut Context<Self>,
     ) -> Self {
         let history = workspace_config.benchmark_runs.clone();
         let receiver = command_bus.subscribe();
+        let this = cx.entity().downgrade();
+        let theme_subscription = theme_registry.observe(move |change, cx| {
+            let _ = this.update(cx, |app, cx| {
+                app.theme_variant = change.current;
+                app.persist_state(cx);
+                cx.notify();
+            });
+        });
+        let this = cx.entity().downgrade();
+        let config_watcher = config_store
+            .watch(
+                cx,
+                move |config, cx| {
+                    let _ = this.update(cx, |app, cx| app.apply_external_config(config, cx));
+                },
+                |err, _| eprintln!("failed to reload workspace configuration: {err}"),
+            )
+            .map_err(|err| eprintln!("failed to watch workspace configuration: {err}"))
+            .ok();
         let mut app = Self {
             theme_variant: theme_registry.active(),
             theme_registry,
@@ -652,12 +702,15 @@ impl WorkbenchApp {
             filter: FilterState::default(),
             chart_tick: 0,
             performance: PerformanceState::new(history),
+            _theme_subscription: theme_subscription,
+            _config_watcher: config_watcher,
         };
         if let Some(state) = app.workspace_config.layout_state.clone() {
             app.apply_persisted_state(&state);
         }
         app.performance
             .set_history(app.workspace_config.benchmark_runs.clone());
+        app.localization.icon_labels(&app.locale).install(cx);
         app
     }
 
@@ -694,11 +747,10 @@ impl WorkbenchApp {
                     }
                 }
                 "theme" => {
-                    self.theme_variant = match value {
-                        "Dark" => ThemeVariant::Dark,
-                        "HighContrast" => ThemeVariant::HighContrast,
-                        _ => ThemeVariant::Light,
-                    };
+                    self.theme_variant = self
+                        .theme_registry
+                        .variant_from_str(value)
+                        .unwrap_or(ThemeVariant::Light);
                 }
                 _ => {}
             }
@@ -707,18 +759,53 @@ impl WorkbenchApp {
 
     fn persist_state(&mut self, cx: &mut Context<Self>) {
         let snapshot = format!(
-            "tab:{:?};sidebar:{};epoch:{};locale:{};theme:{:?}",
+            "tab:{:?};sidebar:{};epoch:{};locale:{};theme:{}",
             self.selected_tab,
             if self.sidebar_collapsed { 1 } else { 0 },
             self.layout_epoch,
             self.locale,
-            self.theme_variant,
+            self.theme_variant.as_str(),
         );
-        self.workspace_config.layout_state = Some(snapshot.clone());
-        cx.set_global(LayoutState(snapshot));
-        if let Err(err) = self.config_store.save(&self.workspace_config) {
-            eprintln!("failed to persist layout: {err}");
-        }
+        let preference = self.theme_registry.preference().to_string();
+        cx.set_global(LayoutState(snapshot.clone()));
+        self.update_config(
+            "persist layout",
+            move |config| {
+                config.layout_state = Some(snapshot.clone());
+                config.theme_preference = Some(preference.clone());
+            },
+            cx,
+        );
+    }
+
+    /// Applies `change` to the in-memory config now, and to the stored one,
+    /// merged with whatever other processes saved since, in the background.
+    fn update_config(
+        &mut self,
+        action: &'static str,
+        change: impl Fn(&mut WorkspaceConfig) + Send + 'static,
+        cx: &mut Context<Self>,
+    ) {
+        change(&mut self.workspace_config);
+        let saved = self.config_store.update_in_background(cx, change);
+        cx.spawn(async move |this, cx| match saved.await {
+            Ok(config) => {
+                let _ = this.update(cx, |app, _| app.workspace_config = config);
+            }
+            Err(err) => eprintln!("failed to {action}: {err}"),
+        })
+        .detach();
+    }
+
+    /// Adopts a configuration another process saved. The layout this window
+    /// shows is left alone; benchmark history and motion settings follow.
+    fn apply_external_config(&mut self, config: &WorkspaceConfig, cx: &mut Context<Self>) {
+        let transition = self.theme_registry.transition();
+        self.theme_registry
+            .set_transition(transition.with_reduced_motion(config.reduce_motion));
+        self.performance.set_history(config.benchmark_runs.clone());
+        self.workspace_config = config.clone();
+        cx.notify();
     }
 
     fn process_commands(&mut self, window: &mut Window, cx: &mut Context<Self>) {
@@ -755,17 +842,17 @@ impl WorkbenchApp {
 
     fn run_benchmark_suite(&mut self, window: &mut Window, cx: &mut Context<Self>) {
         let run = self.performance.run_full_suite();
-        self.workspace_config.record_benchmark(run.clone());
-        if let Err(err) = self.config_store.save(&self.workspace_config) {
-            eprintln!("failed to persist benchmark run: {err}");
-        }
-
         let message = format!(
             "Scroll {:.1} FPS • Typing {:.1} ms • LSP {:.1} ms",
             run.virtualization.avg_scroll_fps,
             run.editor.avg_typing_latency_ms,
             run.editor.avg_lsp_latency_ms
         );
+        self.update_config(
+            "persist benchmark run",
+            move |config| config.record_benchmark(run.clone()),
+            cx,
+        );
 
         window.push_notification(
             Notification::new("Benchmark suite complete")
@@ -779,10 +866,11 @@ impl WorkbenchApp {
 
     fn clear_benchmark_history(&mut self, cx: &mut Context<Self>) {
         self.performance.clear_history();
-        self.workspace_config.benchmark_runs.clear();
-        if let Err(err) = self.config_store.save(&self.workspace_config) {
-            eprintln!("failed to clear benchmark history: {err}");
-        }
+        self.update_config(
+            "clear benchmark history",
+            |config| config.benchmark_runs.clear(),
+            cx,
+        );
         cx.notify();
     }
 
@@ -812,17 +900,34 @@ impl WorkbenchApp {
         } else {
             langid!("en-US")
         };
+        self.localization.icon_labels(&self.locale).install(cx);
         self.persist_state(cx);
         cx.notify();
     }
 
     fn cycle_theme(&mut self, cx: &mut Context<Self>) {
-        self.theme_variant = match self.theme_variant {
-            ThemeVariant::Light => ThemeVariant::Dark,
-            ThemeVariant::Dark => ThemeVariant::HighContrast,
-            ThemeVariant::HighContrast => ThemeVariant::Light,
-        };
-        self.theme_registry.apply(self.theme_variant, cx);
+        let next = self.theme_registry.next_variant(self.theme_variant);
+        self.theme_registry
+            .set_preference(ThemePreference::Fixed(next), cx);
+    }
+
+    fn follow_system_theme(&mut self, cx: &mut Context<Self>) {
+        self.theme_registry
+            .set_preference(ThemePreference::SYSTEM, cx);
+        // The theme observer only fires when the variant changes, but the
+        // preference itself still needs saving.
+        self.persist_state(cx);
+    }
+
+    fn set_reduce_motion(&mut self, reduce_motion: bool, cx: &mut Context<Self>) {
+        self.update_config(
+            "persist motion preference",
+            move |config| config.reduce_motion = reduce_motion,
+            cx,
+        );
+        let transition = self.theme_registry.transition();
+        self.theme_registry
+            .set_transition(transition.with_reduced_motion(reduce_motion));
         self.persist_state(cx);
         cx.notify();
     }
@@ -1742,12 +1847,29 @@ impl WorkbenchApp {
                 v_flex()
                     .gap_2()
                     .child(Text::new(actions_label).text_sm().font_weight_semibold())
-                    .child(Button::new("sidebar-theme").label("Cycle theme").on_click(
-                        cx.listener(|this, _, _, cx| {
-                            this.command_bus.publish(WorkbenchCommand::ToggleTheme);
-                            cx.notify();
-                        }),
-                    )),
+                    .child(
+                        Button::new("sidebar-theme")
+                            .label("Cycle theme")
+                            .on_click(cx.listener(|this, _, _, cx| {
+                                this.command_bus.publish(WorkbenchCommand::ToggleTheme);
+                                cx.notify();
+                            })),
+                    )
+                    .child(
+                        Button::new("sidebar-system-theme")
+                            .label("Follow system theme")
+                            .on_click(cx.listener(|this, _, _, cx| {
+                                this.follow_system_theme(cx);
+                            })),
+                    )
+                    .child(
+                        Switch::new("sidebar-reduce-motion")
+                            .checked(self.workspace_config.reduce_motion)
+                            .label(Text::new("Reduce motion"))
+                            .on_click(cx.listener(|this, state, _, cx| {
+                                this.set_reduce_motion(*state, cx);
+                            })),
+                    ),
             )
     }
 
@@ -1809,7 +1931,8 @@ impl WorkbenchApp {
                     .trend("200 responses")
                     .icon(IconName::Stars),
             )
-            .push(KpiMetric::new("18", "Open incidents").trend("3 blocking"));
+            .push(KpiMetric::new("18", "Open incidents").trend("3 blocking"))
+            .icon_tooltips("workbench-kpis");
 
         div()
             .bg(cx.theme().popover)
@@ -2366,6 +2489,7 @@ let snapshot = cx.global::<LayoutState>().0.clone();"#,
 
 impl gpui::Render for WorkbenchApp {
     fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        self.theme_registry.scope_window(window, cx);
         self.render_view(window, cx)
     }
 }
@@ -2395,6 +2519,7 @@ impl GalleryPreview {
 
 impl gpui::Render for GalleryPreview {
     fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        self.theme_registry.scope_window(window, cx);
         v_flex()
             .size_full()
             .gap_4()
@@ -2407,12 +2532,13 @@ impl gpui::Render for GalleryPreview {
                     .child(Text::new("Gallery preview").size(20.0).font_weight_bold())
                     .child(
                         ThemeSwitch::new("gallery-preview-theme", self.theme_registry.clone())
-                            .label("Theme"),
+                            .label("Theme")
+                            .window_scoped(),
                     ),
             )
             .child(
                 Text::new(
-                    "This lightweight preview shares the same theme registry and localization as the gallery binary.",
+                    "This lightweight preview shares the same theme registry and localization as the gallery binary. Its theme switch only affects this window, so it can be compared side-by-side with the workbench.",
                 )
                 .text_color(cx.theme().muted_foreground),
             )
@@ -2425,6 +2551,7 @@ registry.install(cx);
 let preview = GalleryPreview::new(registry.clone(), localization.clone());"#,
                 &[
                     "Install the registry once at startup and clone handles for additional windows.",
+                    "Call `registry.scope_window(window, cx)` at the top of each root view's `render` so per-window themes take effect.",
                     "Share localization state so component labels match the active language.",
                 ],
                 &[
@@ -2452,6 +2579,7 @@ impl DemoLauncher {
 
 impl gpui::Render for DemoLauncher {
     fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        self.theme_registry.scope_window(window, cx);
         v_flex()
             .gap_4()
             .p_6()
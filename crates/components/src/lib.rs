@@ -3,10 +3,14 @@
 
 pub mod docs;
 
-use designsystem::{IconLoader, IconName, ThemeRegistry, ThemeVariant};
+use designsystem::{
+    ActiveIconLabels as _, ElevationToken, IconVariant, SpacingToken, TextRole, ThemePreference,
+    ThemeRegistry, ThemeVariant, TokenStyled as _,
+};
 use gpui::{
-    platform::keystroke::Keystroke, prelude::FluentBuilder as _, px, AnyElement, App, IntoElement,
-    ParentElement, RenderOnce, SharedString, Window,
+    div, img, platform::keystroke::Keystroke, prelude::FluentBuilder as _, px, AnyElement, App,
+    ElementId, Hsla, InteractiveElement as _, IntoElement, ParentElement, RenderOnce, SharedString,
+    StatefulInteractiveElement as _, Window,
 };
 use gpui_component::{
     button::{Button, ButtonVariants as _},
@@ -15,16 +19,52 @@ use gpui_component::{
     styled::{h_flex, v_flex, StyledExt as _},
     switch::Switch,
     text::Text,
+    tooltip::Tooltip,
     Icon,
 };
 use smallvec::SmallVec;
 
+/// Renders `icon` at `size` pixels, tinted with `color` unless its artwork
+/// keeps its own colors, in which case it is drawn as an image. With a
+/// `tooltip` id, the icon's accessible label from `cx.icon_label` is shown as
+/// its tooltip; the id must be unique among the icon's siblings.
+fn icon_element(
+    icon: IconVariant,
+    size: u16,
+    color: Option<Hsla>,
+    tooltip: Option<ElementId>,
+    cx: &App,
+) -> AnyElement {
+    let label = cx.icon_label(icon.name());
+    let icon = icon.at_size(size);
+    let size = px(f32::from(size));
+    let element = if icon.is_multicolor() {
+        img(icon.asset_path()).size(size).into_any_element()
+    } else {
+        let mut element = Icon::default().path(icon.asset_path()).size(size);
+        if let Some(color) = color {
+            element = element.text_color(color);
+        }
+        element.into_any_element()
+    };
+    let Some(id) = tooltip else {
+        return element;
+    };
+    div()
+        .id(id)
+        .child(element)
+        .tooltip(move |window, cx| Tooltip::new(label.clone()).build(window, cx))
+        .into_any_element()
+}
+
 /// Card component tailored for dashboard summaries.
 #[derive(Default, IntoElement)]
 pub struct DashboardCard {
     title: SharedString,
     description: Option<SharedString>,
-    icon: Option<IconName>,
+    icon: Option<IconVariant>,
+    icon_tooltip: Option<ElementId>,
+    elevation: ElevationToken,
     actions: SmallVec<[AnyElement; 2]>,
     body: SmallVec<[AnyElement; 4]>,
 }
@@ -46,10 +86,27 @@ impl DashboardCard {
         self
     }
 
-    /// Displays the given icon in the card header.
+    /// Displays the given icon in the card header; pass
+    /// `IconName::Search.variant(IconStyle::Filled)` for another style.
     #[must_use]
-    pub fn icon(mut self, icon: IconName) -> Self {
-        self.icon = Some(icon);
+    pub fn icon(mut self, icon: impl Into<IconVariant>) -> Self {
+        self.icon = Some(icon.into());
+        self
+    }
+
+    /// Shows the icon's accessible label as a tooltip. `id` identifies the
+    /// hover target and must be unique among the card's siblings.
+    #[must_use]
+    pub fn icon_tooltip(mut self, id: impl Into<ElementId>) -> Self {
+        self.icon_tooltip = Some(id.into());
+        self
+    }
+
+    /// Sets the theme elevation used for the card shadow; cards are raised by
+    /// default.
+    #[must_use]
+    pub fn elevation(mut self, elevation: ElevationToken) -> Self {
+        self.elevation = elevation;
         self
     }
 
@@ -70,35 +127,41 @@ impl ParentElement for DashboardCard {
 impl RenderOnce for DashboardCard {
     fn render(self, _window: &mut Window, cx: &mut App) -> AnyElement {
         let title_block = v_flex()
-            .gap_1()
-            .child(Text::new(self.title.clone()).size(18.0))
+            .gap_token(SpacingToken::XS, cx)
+            .child(Text::new(self.title.clone()).text_style(TextRole::Title, cx))
             .when_some(self.description.clone(), |col, description| {
                 col.child(
                     Text::new(description)
                         .text_color(cx.theme().muted_foreground)
-                        .size(13.0),
+                        .text_style(TextRole::Label, cx),
                 )
             });
 
         let header = h_flex()
-            .gap_3()
+            .gap_token(SpacingToken::MD, cx)
             .items_center()
             .when_some(self.icon, |row, icon| {
-                row.child(
-                    Icon::default()
-                        .path(IconLoader::asset_path(icon))
-                        .size(px(20.0)),
-                )
+                row.child(icon_element(icon, 20, None, self.icon_tooltip.clone(), cx))
             })
             .child(title_block)
             .when(!self.actions.is_empty(), |row| {
-                row.child(h_flex().gap_2().ml_auto().children(self.actions))
+                row.child(
+                    h_flex()
+                        .gap_token(SpacingToken::SM, cx)
+                        .ml_auto()
+                        .children(self.actions),
+                )
             });
 
-        GroupBox::new()
-            .fill()
-            .title(header)
-            .child(v_flex().gap_3().children(self.body))
+        div()
+            .rounded(cx.theme().radius_lg)
+            .elevation(self.elevation, cx)
+            .child(
+                GroupBox::new()
+                    .fill()
+                    .title(header)
+                    .child(v_flex().gap_token(SpacingToken::MD, cx).children(self.body)),
+            )
             .into_any_element()
     }
 }
@@ -113,7 +176,7 @@ pub struct KpiMetric {
     /// Optional supplemental text (e.g. delta).
     pub trend: Option<SharedString>,
     /// Optional icon displayed above the value.
-    pub icon: Option<IconName>,
+    pub icon: Option<IconVariant>,
 }
 
 impl KpiMetric {
@@ -137,8 +200,8 @@ impl KpiMetric {
 
     /// Adds an icon to the KPI block.
     #[must_use]
-    pub fn icon(mut self, icon: IconName) -> Self {
-        self.icon = Some(icon);
+    pub fn icon(mut self, icon: impl Into<IconVariant>) -> Self {
+        self.icon = Some(icon.into());
         self
     }
 }
@@ -147,6 +210,7 @@ impl KpiMetric {
 #[derive(Default, IntoElement)]
 pub struct KpiGrid {
     metrics: SmallVec<[KpiMetric; 4]>,
+    icon_tooltips: Option<ElementId>,
 }
 
 impl KpiGrid {
@@ -156,6 +220,15 @@ impl KpiGrid {
         self.metrics.push(metric);
         self
     }
+
+    /// Shows each metric icon's accessible label as a tooltip. `id` must be
+    /// unique among the grid's siblings; the icons are identified by their
+    /// position within it.
+    #[must_use]
+    pub fn icon_tooltips(mut self, id: impl Into<ElementId>) -> Self {
+        self.icon_tooltips = Some(id.into());
+        self
+    }
 }
 
 impl RenderOnce for KpiGrid {
@@ -163,19 +236,27 @@ impl RenderOnce for KpiGrid {
         let cells = self
             .metrics
             .into_iter()
-            .map(|metric| {
-                let mut block = v_flex().gap_1();
+            .enumerate()
+            .map(|(index, metric)| {
+                let mut block = v_flex().gap_token(SpacingToken::XS, cx);
                 if let Some(icon) = metric.icon {
-                    block = block.child(
-                        Icon::default()
-                            .path(IconLoader::asset_path(icon))
-                            .size_5()
-                            .text_color(cx.theme().accent),
-                    );
+                    let tooltip = self.icon_tooltips.clone().map(|grid| {
+                        ElementId::NamedChild(Box::new(grid), format!("icon-{index}").into())
+                    });
+                    block =
+                        block.child(icon_element(icon, 20, Some(cx.theme().accent), tooltip, cx));
                 }
                 block
-                    .child(Text::new(metric.value).size(22.0).font_weight_bold())
-                    .child(Text::new(metric.label).text_color(cx.theme().muted_foreground))
+                    .child(
+                        Text::new(metric.value)
+                            .text_style(TextRole::Title, cx)
+                            .font_weight_bold(),
+                    )
+                    .child(
+                        Text::new(metric.label)
+                            .text_style(TextRole::Label, cx)
+                            .text_color(cx.theme().muted_foreground),
+                    )
                     .when_some(metric.trend, |col, trend| {
                         col.child(Text::new(trend).text_color(cx.theme().accent))
                     })
@@ -185,8 +266,8 @@ impl RenderOnce for KpiGrid {
 
         h_flex()
             .flex_wrap()
-            .gap_6()
-            .child(v_flex().gap_4().children(cells))
+            .gap_token(SpacingToken::XL, cx)
+            .child(v_flex().gap_token(SpacingToken::LG, cx).children(cells))
             .into_any_element()
     }
 }
@@ -224,28 +305,32 @@ impl ParentElement for DockLayoutPanel {
 impl RenderOnce for DockLayoutPanel {
     fn render(self, _window: &mut Window, cx: &mut App) -> AnyElement {
         h_flex()
-            .gap_4()
+            .gap_token(SpacingToken::LG, cx)
             .child(
                 v_flex()
-                    .gap_3()
+                    .gap_token(SpacingToken::MD, cx)
                     .w(px(220.0))
                     .bg(cx.theme().muted)
-                    .p_4()
+                    .p_token(SpacingToken::LG, cx)
                     .rounded(cx.theme().radius)
                     .children(self.sidebar),
             )
             .child(
                 v_flex()
-                    .gap_4()
+                    .gap_token(SpacingToken::LG, cx)
                     .flex_1()
-                    .child(h_flex().gap_2().when(!self.toolbar.is_empty(), |row| {
-                        row.children(self.toolbar.clone())
-                    }))
+                    .child(
+                        h_flex()
+                            .gap_token(SpacingToken::SM, cx)
+                            .when(!self.toolbar.is_empty(), |row| {
+                                row.children(self.toolbar.clone())
+                            }),
+                    )
                     .child(
                         v_flex()
-                            .gap_4()
+                            .gap_token(SpacingToken::LG, cx)
                             .bg(cx.theme().popover)
-                            .p_6()
+                            .p_token(SpacingToken::XL, cx)
                             .rounded(cx.theme().radius_lg)
                             .children(self.content),
                     ),
@@ -260,6 +345,7 @@ pub struct ThemeSwitch {
     id: SharedString,
     registry: ThemeRegistry,
     label: Option<SharedString>,
+    window_scoped: bool,
 }
 
 impl ThemeSwitch {
@@ -270,6 +356,7 @@ impl ThemeSwitch {
             id: id.into(),
             registry,
             label: None,
+            window_scoped: false,
         }
     }
 
@@ -279,12 +366,22 @@ impl ThemeSwitch {
         self.label = Some(label.into());
         self
     }
+
+    /// Pins only the containing window to the chosen theme instead of
+    /// changing the workspace preference. The window's root view must call
+    /// [`ThemeRegistry::scope_window`].
+    #[must_use]
+    pub fn window_scoped(mut self) -> Self {
+        self.window_scoped = true;
+        self
+    }
 }
 
 impl RenderOnce for ThemeSwitch {
     fn render(self, window: &mut Window, cx: &mut App) -> AnyElement {
         let registry = self.registry.clone();
-        let checked = !matches!(registry.active(), ThemeVariant::Light);
+        let window_scoped = self.window_scoped;
+        let checked = !matches!(registry.window_theme(window), ThemeVariant::Light);
 
         Switch::new(self.id.clone())
             .checked(checked)
@@ -295,10 +392,18 @@ impl RenderOnce for ThemeSwitch {
                 } else {
                     ThemeVariant::Light
                 };
-                registry.apply(variant, cx);
-                window.refresh();
+                if window_scoped {
+                    registry.set_window_theme(window, Some(variant));
+                } else {
+                    registry.set_window_theme(window, None);
+                    registry.set_preference(ThemePreference::Fixed(variant), cx);
+                }
+            })
+            .tooltip(if window_scoped {
+                "Toggle theme for this window"
+            } else {
+                "Toggle workspace theme"
             })
-            .tooltip("Toggle workspace theme")
             .build(window, cx)
     }
 }
@@ -309,7 +414,7 @@ pub struct CommandPaletteTrigger {
     id: SharedString,
     label: SharedString,
     shortcut: Option<SharedString>,
-    icon: IconName,
+    icon: IconVariant,
 }
 
 impl CommandPaletteTrigger {
@@ -319,13 +424,13 @@ impl CommandPaletteTrigger {
         id: impl Into<SharedString>,
         label: impl Into<SharedString>,
         shortcut: Option<impl Into<SharedString>>,
-        icon: IconName,
+        icon: impl Into<IconVariant>,
     ) -> Self {
         Self {
             id: id.into(),
             label: label.into(),
             shortcut: shortcut.map(Into::into),
-            icon,
+            icon: icon.into(),
         }
     }
 }
@@ -336,7 +441,7 @@ impl RenderOnce for CommandPaletteTrigger {
             .ghost()
             .icon(
                 Icon::default()
-                    .path(IconLoader::asset_path(self.icon))
+                    .path(self.icon.at_size(16).asset_path())
                     .text_color(cx.theme().foreground),
             )
             .label(self.label.clone());
@@ -345,7 +450,7 @@ impl RenderOnce for CommandPaletteTrigger {
             if let Ok(key) = Keystroke::parse(&shortcut) {
                 button = button.child(
                     h_flex()
-                        .gap_1()
+                        .gap_token(SpacingToken::XS, cx)
                         .ml_3()
                         .child(Text::new("/"))
                         .child(Kbd::new(key).appearance(true)),
@@ -366,6 +471,7 @@ mod tests {
         let mut card = DashboardCard::new("Title");
         card.extend([Text::new("Body").into_any_element()]);
         assert_eq!(card.body.len(), 1);
+        assert_eq!(card.elevation, ElevationToken::Raised);
     }
 
     #[test]
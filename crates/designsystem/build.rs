@@ -1,31 +1,175 @@
 use std::{
+    collections::BTreeMap,
     env,
     fs::{self, File},
     io::{self, Write},
     path::Path,
+    process,
 };
 
 use heck::ToUpperCamelCase;
+use serde::Deserialize;
+
+#[allow(dead_code)]
+#[path = "src/svg.rs"]
+mod svg;
+
+use svg::{optimize_svg, SvgOptions};
+
+/// Styles an artwork file may declare with a `.<style>` suffix.
+const STYLES: [(&str, &str); 3] = [
+    ("outline", "Outline"),
+    ("filled", "Filled"),
+    ("duotone", "Duotone"),
+];
+
+/// One SVG file, named `<stem>[.<style>][@<size>].svg`.
+struct Artwork {
+    file_name: String,
+    style: &'static str,
+    size: Option<u16>,
+    /// Validated and minified markup.
+    svg: String,
+    multicolor: bool,
+}
+
+/// All artwork sharing a stem, which becomes one `IconName` variant.
+struct Icon {
+    variant: String,
+    base: Option<Artwork>,
+    artwork: Vec<Artwork>,
+}
+
+/// Contents of an optional `<stem>.toml` sidecar.
+#[derive(Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct Metadata {
+    label: Option<String>,
+    category: Option<String>,
+    tags: Vec<String>,
+    synonyms: Vec<String>,
+}
 
 fn main() -> io::Result<()> {
     let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
     let icons_dir = Path::new(&manifest_dir).join("icons");
     println!("cargo:rerun-if-changed={}", icons_dir.display());
+    println!("cargo:rerun-if-env-changed=DESIGNSYSTEM_ICON_REPORT");
 
     let mut entries: Vec<_> = fs::read_dir(&icons_dir)?
         .filter_map(|entry| entry.ok())
-        .filter(|entry| {
-            entry
-                .path()
-                .extension()
-                .map(|ext| ext == "svg")
-                .unwrap_or(false)
-        })
         .collect();
 
-    entries.sort_by(|a, b| a.file_name().cmp(&b.file_name()));
+    entries.sort_by_key(|entry| entry.file_name());
+
+    let mut icons: BTreeMap<String, Icon> = BTreeMap::new();
+    let mut metadata: BTreeMap<String, Metadata> = BTreeMap::new();
+    let mut errors = Vec::new();
+    let mut report = String::new();
+    for entry in &entries {
+        let path = entry.path();
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("svg") => {}
+            Some("toml") => {
+                let file_name = entry.file_name().to_string_lossy().into_owned();
+                let stem = file_name.trim_end_matches(".toml").to_owned();
+                match toml::from_str(&fs::read_to_string(&path)?) {
+                    Ok(parsed) => {
+                        metadata.insert(stem, parsed);
+                    }
+                    Err(err) => errors.push(format!("icons/{file_name}: {}", err.message())),
+                }
+                continue;
+            }
+            _ => continue,
+        }
+        let file_name = entry.file_name().to_string_lossy().into_owned();
+        let (stem, style, size) = match parse_file_name(&file_name) {
+            Ok((stem, style, size)) => (stem.to_owned(), style, size),
+            Err(err) => {
+                errors.push(format!("icons/{file_name}: {err}"));
+                continue;
+            }
+        };
+        let source = fs::read_to_string(entry.path())?;
+        // Only duotone artwork may paint with its own colors.
+        let options = if style == "Duotone" {
+            SvgOptions::MULTICOLOR
+        } else {
+            SvgOptions::MONOCHROME
+        };
+        let optimized = match optimize_svg(&source, options) {
+            Ok(optimized) => optimized,
+            Err(err) => {
+                errors.push(format!("icons/{file_name}: {err}"));
+                continue;
+            }
+        };
+        report.push_str(&format!(
+            "{file_name}: {} -> {} bytes (saved {})\n",
+            optimized.original_bytes,
+            optimized.svg.len(),
+            optimized.saved_bytes()
+        ));
+        let artwork = Artwork {
+            multicolor: optimized.multicolor,
+            svg: optimized.svg,
+            file_name,
+            style,
+            size,
+        };
+        let icon = icons.entry(stem.clone()).or_insert_with(|| Icon {
+            variant: stem.replace('-', " ").to_upper_camel_case(),
+            base: None,
+            artwork: Vec::new(),
+        });
+        // `x.svg` and `x.outline.svg` both name the base artwork, so which
+        // one won would depend on directory order.
+        let duplicate = icon
+            .base
+            .iter()
+            .chain(&icon.artwork)
+            .find(|other| other.style == style && other.size == size);
+        if let Some(duplicate) = duplicate {
+            errors.push(format!(
+                "icons/{}: same style and size as icons/{}; keep only one of them",
+                artwork.file_name, duplicate.file_name
+            ));
+            continue;
+        }
+        if style == "Outline" && size.is_none() {
+            icon.base = Some(artwork);
+        } else {
+            icon.artwork.push(artwork);
+        }
+    }
+    for (stem, icon) in &icons {
+        if icon.base.is_none() {
+            errors.push(format!(
+                "icons/{stem}.svg: missing; style and size variants need a base icon to fall back to"
+            ));
+        }
+    }
+    for stem in metadata.keys() {
+        if !icons.contains_key(stem) {
+            errors.push(format!("icons/{stem}.toml: no icon named '{stem}'"));
+        }
+    }
+    if !errors.is_empty() {
+        for error in &errors {
+            eprintln!("error: {error}");
+        }
+        process::exit(1);
+    }
 
     let out_dir = env::var("OUT_DIR").unwrap();
+    fs::write(Path::new(&out_dir).join("icon_report.txt"), &report)?;
+    if env::var_os("DESIGNSYSTEM_ICON_REPORT").is_some() {
+        for line in report.lines() {
+            println!("cargo:warning={line}");
+        }
+    }
+
     let dest_path = Path::new(&out_dir).join("icon_data.rs");
     let mut file = File::create(dest_path)?;
 
@@ -33,87 +177,145 @@ fn main() -> io::Result<()> {
         file,
         "/// Strongly typed identifier for a design system icon.\n#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]\npub enum IconName {{"
     )?;
-
-    for entry in &entries {
-        let file_name = entry.file_name();
-        let file_name = file_name.to_string_lossy();
-        let stem = Path::new(&*file_name)
-            .file_stem()
-            .unwrap()
-            .to_string_lossy();
-        let variant = stem.replace('-', " ").to_upper_camel_case();
+    for (stem, icon) in &icons {
         writeln!(file, "    /// Icon asset generated from `{stem}.svg`.")?;
-        writeln!(file, "    {variant},")?;
+        writeln!(file, "    {},", icon.variant)?;
     }
-
     writeln!(file, "}}\n")?;
 
     writeln!(
         file,
-        "#[allow(missing_docs)]\nimpl IconName {{\n    pub const ALL: &'static [IconName] = &["
+        "#[allow(missing_docs)]\nimpl IconName {{\n    pub const ALL: &[IconName] = &["
     )?;
-    for entry in &entries {
-        let file_name = entry.file_name();
-        let stem = Path::new(&file_name).file_stem().unwrap().to_string_lossy();
-        let variant = stem.replace('-', " ").to_upper_camel_case();
-        writeln!(file, "        IconName::{variant},")?;
+    for icon in icons.values() {
+        writeln!(file, "        IconName::{},", icon.variant)?;
     }
     writeln!(file, "    ];\n")?;
 
+    writeln!(
+        file,
+        "    /// Returns the file stem the icon was generated from.\n    #[must_use]\n    pub const fn stem(self) -> &'static str {{"
+    )?;
+    writeln!(file, "        match self {{")?;
+    for (stem, icon) in &icons {
+        writeln!(
+            file,
+            "            IconName::{} => \"{stem}\",",
+            icon.variant
+        )?;
+    }
+    writeln!(file, "        }}\n    }}\n")?;
+
     writeln!(file, "    pub const fn asset_path(self) -> &'static str {{")?;
     writeln!(file, "        match self {{")?;
-    for entry in &entries {
-        let file_name = entry.file_name();
-        let file_name = file_name.to_string_lossy();
-        let stem = Path::new(&*file_name)
-            .file_stem()
-            .unwrap()
-            .to_string_lossy();
-        let variant = stem.replace('-', " ").to_upper_camel_case();
+    for icon in icons.values() {
+        let base = icon.base.as_ref().unwrap();
         writeln!(
             file,
-            "            IconName::{variant} => \"designsystem/icons/{file_name}\","
+            "            IconName::{} => \"designsystem/icons/{}\",",
+            icon.variant, base.file_name
         )?;
     }
     writeln!(file, "        }}\n    }}\n")?;
 
     writeln!(file, "    pub const fn svg(self) -> &'static str {{")?;
     writeln!(file, "        match self {{")?;
-    for entry in &entries {
-        let file_name = entry.file_name();
-        let file_name = file_name.to_string_lossy();
-        let stem = Path::new(&*file_name)
-            .file_stem()
-            .unwrap()
-            .to_string_lossy();
-        let variant = stem.replace('-', " ").to_upper_camel_case();
-        let icon_path = icons_dir
-            .strip_prefix(&manifest_dir)
-            .unwrap()
-            .join(&*file_name);
+    for icon in icons.values() {
+        let base = icon.base.as_ref().unwrap();
         writeln!(
             file,
-            "            IconName::{variant} => include_str!(concat!(env!(\"CARGO_MANIFEST_DIR\"), \"/{}\")),",
-            icon_path.display()
+            "            IconName::{} => {:?},",
+            icon.variant, base.svg
         )?;
     }
+    writeln!(file, "        }}\n    }}\n")?;
+
     writeln!(
         file,
-        "        }}\n    }}\n}}
-"
+        "    /// Returns the label, category, tags, and synonyms from the icon's\n    /// `<stem>.toml` sidecar, with the label derived from the stem when the\n    /// sidecar omits it.\n    #[must_use]\n    pub const fn metadata(self) -> &'static IconMetadata {{"
     )?;
+    writeln!(file, "        match self {{")?;
+    for (stem, icon) in &icons {
+        let meta = metadata.remove(stem).unwrap_or_default();
+        let label = meta.label.unwrap_or_else(|| derive_label(stem));
+        let category = meta.category.unwrap_or_else(|| "general".to_owned());
+        writeln!(
+            file,
+            "            IconName::{} => &IconMetadata {{ label: {label:?}, category: {category:?}, tags: &{:?}, synonyms: &{:?} }},",
+            icon.variant, meta.tags, meta.synonyms
+        )?;
+    }
+    writeln!(file, "        }}\n    }}\n}}\n")?;
 
     writeln!(
         file,
-        "/// Lookup table that maps icon stems to strongly typed names.\npub(crate) const ICON_NAMES: &[(&'static str, IconName)] = &["
+        "/// Lookup table that maps icon stems to strongly typed names.\npub(crate) const ICON_NAMES: &[(&str, IconName)] = &["
     )?;
-    for entry in &entries {
-        let file_name = entry.file_name();
-        let stem = Path::new(&file_name).file_stem().unwrap().to_string_lossy();
-        let variant = stem.replace('-', " ").to_upper_camel_case();
-        writeln!(file, "    (\"{stem}\", IconName::{variant}),")?;
+    for (stem, icon) in &icons {
+        writeln!(file, "    (\"{stem}\", IconName::{}),", icon.variant)?;
+    }
+    writeln!(file, "];\n")?;
+
+    writeln!(
+        file,
+        "/// Every artwork file, base icons first within each icon.\npub(crate) const ICON_ARTWORK: &[IconArtwork] = &["
+    )?;
+    for icon in icons.values() {
+        for artwork in icon.base.iter().chain(&icon.artwork) {
+            let size = match artwork.size {
+                Some(size) => format!("Some({size})"),
+                None => "None".to_owned(),
+            };
+            writeln!(
+                file,
+                "    IconArtwork {{ icon: IconName::{}, style: IconStyle::{}, size: {size}, asset_path: \"designsystem/icons/{}\", svg: {:?}, multicolor: {} }},",
+                icon.variant,
+                artwork.style,
+                artwork.file_name,
+                artwork.svg,
+                artwork.multicolor
+            )?;
+        }
     }
     writeln!(file, "];\n")?;
 
     Ok(())
 }
+
+/// Turns `product-palette` into `Product palette`.
+fn derive_label(stem: &str) -> String {
+    let words = stem.replace(['-', '_'], " ");
+    let mut chars = words.chars();
+    chars
+        .next()
+        .map(|first| first.to_uppercase().chain(chars).collect())
+        .unwrap_or_default()
+}
+
+/// Splits `search.filled@16.svg` into its stem, style, and optional size.
+fn parse_file_name(file_name: &str) -> Result<(&str, &'static str, Option<u16>), String> {
+    let name = file_name.trim_end_matches(".svg");
+    let (name, size) = match name.rsplit_once('@') {
+        Some((name, size)) => {
+            let size = size
+                .parse()
+                .map_err(|_| format!("invalid size '@{size}'; expected a pixel count"))?;
+            (name, Some(size))
+        }
+        None => (name, None),
+    };
+    let (stem, style) = match name.split_once('.') {
+        Some((stem, style)) => {
+            let style = STYLES
+                .iter()
+                .find(|(suffix, _)| *suffix == style)
+                .map(|(_, variant)| *variant)
+                .ok_or_else(|| {
+                    format!("unknown style '.{style}'; expected .outline, .filled or .duotone")
+                })?;
+            (stem, style)
+        }
+        None => (name, "Outline"),
+    };
+    Ok((stem, style, size))
+}
@@ -0,0 +1,244 @@
+//! Theme preferences that can follow the operating system's light/dark
+//! appearance.
+
+use std::{
+    fmt,
+    sync::{Arc, Mutex},
+};
+
+#[cfg(feature = "gpui")]
+use gpui::{App, Subscription, Window, WindowAppearance};
+
+use crate::theme::{ThemeAppearance, ThemeError, ThemeRegistry, ThemeVariant};
+
+/// How the registry chooses the active theme.
+///
+/// Preferences persist as short slugs: a theme slug such as `dark` for a
+/// fixed theme, `system` for the built-in light/dark pair, or
+/// `system:<light>:<dark>` for a custom pair.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ThemePreference {
+    /// Always use the given theme.
+    Fixed(ThemeVariant),
+    /// Follow the operating system appearance.
+    System {
+        /// Theme applied while the system appearance is light.
+        light: ThemeVariant,
+        /// Theme applied while the system appearance is dark.
+        dark: ThemeVariant,
+    },
+}
+
+impl ThemePreference {
+    /// Follows the system with the built-in light and dark themes.
+    pub const SYSTEM: Self = Self::System {
+        light: ThemeVariant::Light,
+        dark: ThemeVariant::Dark,
+    };
+
+    /// Returns the theme to show for the given system appearance.
+    #[must_use]
+    pub const fn resolve(self, appearance: ThemeAppearance) -> ThemeVariant {
+        match (self, appearance) {
+            (Self::Fixed(variant), _)
+            | (Self::System { light: variant, .. }, ThemeAppearance::Light)
+            | (Self::System { dark: variant, .. }, ThemeAppearance::Dark) => variant,
+        }
+    }
+
+    /// Returns `true` when the preference tracks the system appearance.
+    #[must_use]
+    pub const fn follows_system(self) -> bool {
+        matches!(self, Self::System { .. })
+    }
+}
+
+impl Default for ThemePreference {
+    fn default() -> Self {
+        Self::Fixed(ThemeVariant::Light)
+    }
+}
+
+impl fmt::Display for ThemePreference {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Self::Fixed(variant) => f.write_str(variant.as_str()),
+            Self::SYSTEM => f.write_str("system"),
+            Self::System { light, dark } => {
+                write!(f, "system:{}:{}", light.as_str(), dark.as_str())
+            }
+        }
+    }
+}
+
+#[cfg(feature = "gpui")]
+impl From<WindowAppearance> for ThemeAppearance {
+    fn from(appearance: WindowAppearance) -> Self {
+        match appearance {
+            WindowAppearance::Light | WindowAppearance::VibrantLight => ThemeAppearance::Light,
+            WindowAppearance::Dark | WindowAppearance::VibrantDark => ThemeAppearance::Dark,
+        }
+    }
+}
+
+/// Shared record of the operating system's light/dark appearance.
+///
+/// [`ThemeRegistry::follow_system_appearance`] keeps it in sync with GPUI's
+/// window appearance. Tests and headless runs can drive it directly with
+/// [`AppearanceSource::set`], since no platform window is required.
+#[derive(Debug, Clone)]
+pub struct AppearanceSource {
+    current: Arc<Mutex<ThemeAppearance>>,
+}
+
+impl AppearanceSource {
+    /// Creates a source reporting `appearance`.
+    #[must_use]
+    pub fn new(appearance: ThemeAppearance) -> Self {
+        Self {
+            current: Arc::new(Mutex::new(appearance)),
+        }
+    }
+
+    /// Returns the last reported appearance.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the appearance mutex was poisoned.
+    #[must_use]
+    pub fn get(&self) -> ThemeAppearance {
+        *self.current.lock().expect("appearance mutex poisoned")
+    }
+
+    /// Records a new appearance.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the appearance mutex was poisoned.
+    pub fn set(&self, appearance: ThemeAppearance) {
+        *self.current.lock().expect("appearance mutex poisoned") = appearance;
+    }
+}
+
+impl Default for AppearanceSource {
+    fn default() -> Self {
+        Self::new(ThemeAppearance::Light)
+    }
+}
+
+impl ThemeRegistry {
+    /// Returns the theme the current preference selects for the last
+    /// reported system appearance.
+    #[must_use]
+    pub fn preferred_variant(&self) -> ThemeVariant {
+        self.preference().resolve(self.appearance_source().get())
+    }
+
+    /// Parses a persisted preference slug; see [`ThemePreference`] for the
+    /// accepted forms.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ThemeError::UnknownVariant`] when a referenced theme is not
+    /// registered.
+    pub fn preference_from_str(&self, value: &str) -> Result<ThemePreference, ThemeError> {
+        match value.split(':').collect::<Vec<_>>().as_slice() {
+            ["system"] => Ok(ThemePreference::SYSTEM),
+            ["system", light, dark] => Ok(ThemePreference::System {
+                light: self.variant_from_str(light)?,
+                dark: self.variant_from_str(dark)?,
+            }),
+            _ => self.variant_from_str(value).map(ThemePreference::Fixed),
+        }
+    }
+}
+
+#[cfg(feature = "gpui")]
+impl ThemeRegistry {
+    /// Stores `preference` and applies the theme it selects.
+    ///
+    /// Manual theme pickers should pass [`ThemePreference::Fixed`] so a later
+    /// system appearance change does not override the user's choice.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the preference names a theme that is not registered.
+    pub fn set_preference(&self, preference: ThemePreference, cx: &mut App) {
+        self.store_preference(preference);
+        self.apply(self.preferred_variant(), cx);
+    }
+
+    /// Records a new system appearance and switches themes when the
+    /// preference follows the system.
+    ///
+    /// Returns `true` when a different theme was applied.
+    pub fn set_system_appearance(&self, appearance: ThemeAppearance, cx: &mut App) -> bool {
+        self.appearance_source().set(appearance);
+        let variant = self.preferred_variant();
+        if !self.preference().follows_system() || variant == self.active() {
+            return false;
+        }
+        self.apply(variant, cx);
+        true
+    }
+
+    /// Tracks `window`'s appearance, switching themes as the operating system
+    /// toggles between light and dark.
+    ///
+    /// The current appearance is applied immediately. Observing stops when
+    /// the returned subscription is dropped.
+    pub fn follow_system_appearance(&self, window: &mut Window, cx: &mut App) -> Subscription {
+        self.set_system_appearance(window.appearance().into(), cx);
+        let registry = self.clone();
+        window.observe_window_appearance(move |window, cx| {
+            registry.set_system_appearance(window.appearance().into(), cx);
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tokens::light_tokens;
+
+    #[test]
+    fn system_preference_tracks_headless_appearance() {
+        let registry = ThemeRegistry::new();
+        registry.store_preference(ThemePreference::SYSTEM);
+        assert_eq!(registry.preferred_variant(), ThemeVariant::Light);
+
+        registry.appearance_source().set(ThemeAppearance::Dark);
+        assert_eq!(registry.preferred_variant(), ThemeVariant::Dark);
+
+        registry.store_preference(ThemePreference::Fixed(ThemeVariant::HighContrast));
+        assert_eq!(registry.preferred_variant(), ThemeVariant::HighContrast);
+    }
+
+    #[test]
+    fn preferences_round_trip_through_slugs() {
+        let registry = ThemeRegistry::new();
+        let brand = registry
+            .register("brand-light", ThemeAppearance::Light, light_tokens())
+            .unwrap();
+        for preference in [
+            ThemePreference::SYSTEM,
+            ThemePreference::Fixed(ThemeVariant::HighContrast),
+            ThemePreference::System {
+                light: brand,
+                dark: ThemeVariant::Dark,
+            },
+        ] {
+            let slug = preference.to_string();
+            assert_eq!(registry.preference_from_str(&slug).unwrap(), preference);
+        }
+        assert_eq!(
+            ThemePreference::System {
+                light: brand,
+                dark: ThemeVariant::Dark,
+            }
+            .to_string(),
+            "system:brand-light:dark"
+        );
+        assert!(registry.preference_from_str("system:missing:dark").is_err());
+    }
+}
@@ -0,0 +1,378 @@
+//! Color values and the color space conversions shared by the contrast and
+//! palette tooling.
+
+use std::{fmt, str::FromStr};
+
+#[cfg(feature = "gpui")]
+use gpui::{Hsla, Rgba, SharedString};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use thiserror::Error;
+
+use crate::contrast::srgb_to_linear;
+
+/// Number of bisection steps used when searching lightness or chroma. 24
+/// halvings resolve well below one 8-bit sRGB step.
+pub(crate) const SEARCH_STEPS: usize = 24;
+
+/// Error returned when a color string cannot be parsed.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum ColorError {
+    /// Raised when a value is not a `#rrggbb` or `#rrggbbaa` hex string.
+    #[error("expected a '#rrggbb' or '#rrggbbaa' color, got '{0}'")]
+    InvalidHex(String),
+}
+
+/// An sRGB color with an alpha channel.
+///
+/// Colors serialize as lowercase hex strings, omitting the alpha byte when
+/// the color is opaque.
+///
+/// ```
+/// use designsystem::Color;
+///
+/// let primary: Color = "#2563EB".parse()?;
+/// assert_eq!(primary, Color::hex("#2563eb"));
+/// assert_eq!(primary.with_alpha(0x80).to_hex(), "#2563eb80");
+/// # Ok::<(), designsystem::ColorError>(())
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Color {
+    /// Red channel.
+    pub r: u8,
+    /// Green channel.
+    pub g: u8,
+    /// Blue channel.
+    pub b: u8,
+    /// Alpha channel, where 255 is fully opaque.
+    pub a: u8,
+}
+
+impl Color {
+    /// Creates an opaque color from its channels.
+    #[must_use]
+    pub const fn rgb(r: u8, g: u8, b: u8) -> Self {
+        Self::rgba(r, g, b, u8::MAX)
+    }
+
+    /// Creates a color from its channels and alpha.
+    #[must_use]
+    pub const fn rgba(r: u8, g: u8, b: u8, a: u8) -> Self {
+        Self { r, g, b, a }
+    }
+
+    /// Parses a `#rrggbb` or `#rrggbbaa` literal in const contexts, such as
+    /// built-in palettes.
+    ///
+    /// # Panics
+    ///
+    /// Panics when `hex` is not a `#rrggbb` or `#rrggbbaa` string; use
+    /// [`Color::from_hex`] for untrusted input.
+    #[must_use]
+    pub const fn hex(hex: &str) -> Self {
+        const fn digit(byte: u8) -> u8 {
+            match byte {
+                b'0'..=b'9' => byte - b'0',
+                b'a'..=b'f' => byte - b'a' + 10,
+                b'A'..=b'F' => byte - b'A' + 10,
+                _ => panic!("invalid hex digit in color literal"),
+            }
+        }
+
+        let bytes = hex.as_bytes();
+        assert!(
+            (bytes.len() == 7 || bytes.len() == 9) && bytes[0] == b'#',
+            "color literal must be '#rrggbb' or '#rrggbbaa'"
+        );
+        let alpha = if bytes.len() == 9 {
+            digit(bytes[7]) * 16 + digit(bytes[8])
+        } else {
+            u8::MAX
+        };
+        Self::rgba(
+            digit(bytes[1]) * 16 + digit(bytes[2]),
+            digit(bytes[3]) * 16 + digit(bytes[4]),
+            digit(bytes[5]) * 16 + digit(bytes[6]),
+            alpha,
+        )
+    }
+
+    /// Parses a `#rrggbb` or `#rrggbbaa` string, ignoring case.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ColorError::InvalidHex`] for any other input.
+    pub fn from_hex(hex: &str) -> Result<Self, ColorError> {
+        let invalid = || ColorError::InvalidHex(hex.to_owned());
+        let digits = hex.strip_prefix('#').ok_or_else(invalid)?;
+        if !matches!(digits.len(), 6 | 8) || !digits.chars().all(|ch| ch.is_ascii_hexdigit()) {
+            return Err(invalid());
+        }
+        let byte = |index: usize| u8::from_str_radix(&digits[index..index + 2], 16);
+        let alpha = if digits.len() == 8 {
+            byte(6)
+        } else {
+            Ok(u8::MAX)
+        };
+        match (byte(0), byte(2), byte(4), alpha) {
+            (Ok(r), Ok(g), Ok(b), Ok(a)) => Ok(Self::rgba(r, g, b, a)),
+            _ => Err(invalid()),
+        }
+    }
+
+    /// Returns the same color with a different alpha.
+    #[must_use]
+    pub const fn with_alpha(self, a: u8) -> Self {
+        Self { a, ..self }
+    }
+
+    /// Returns `true` when the alpha channel is fully opaque.
+    #[must_use]
+    pub const fn is_opaque(self) -> bool {
+        self.a == u8::MAX
+    }
+
+    /// Returns the alpha channel as a fraction from 0.0 to 1.0.
+    #[must_use]
+    pub fn opacity(self) -> f32 {
+        f32::from(self.a) / 255.0
+    }
+
+    /// Blends towards `other` channel by channel, where an `amount` of 0.0
+    /// returns `self` and 1.0 returns `other`.
+    #[must_use]
+    pub fn mix(self, other: Self, amount: f32) -> Self {
+        let amount = amount.clamp(0.0, 1.0);
+        let channel = |from: u8, to: u8| {
+            let value = f32::from(from) + (f32::from(to) - f32::from(from)) * amount;
+            // Both endpoints are within 0..=255, so the blend is too.
+            #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+            let byte = value.round() as u8;
+            byte
+        };
+        Self::rgba(
+            channel(self.r, other.r),
+            channel(self.g, other.g),
+            channel(self.b, other.b),
+            channel(self.a, other.a),
+        )
+    }
+
+    /// Formats the color as `#rrggbb`, or `#rrggbbaa` when translucent.
+    #[must_use]
+    pub fn to_hex(self) -> String {
+        self.to_string()
+    }
+
+    pub(crate) const fn channels(self) -> [u8; 3] {
+        [self.r, self.g, self.b]
+    }
+
+    pub(crate) const fn from_channels([r, g, b]: [u8; 3]) -> Self {
+        Self::rgb(r, g, b)
+    }
+}
+
+impl fmt::Display for Color {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Self { r, g, b, a } = *self;
+        write!(f, "#{r:02x}{g:02x}{b:02x}")?;
+        if !self.is_opaque() {
+            write!(f, "{a:02x}")?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Color {
+    type Err = ColorError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Self::from_hex(value)
+    }
+}
+
+#[cfg(feature = "gpui")]
+impl From<Color> for SharedString {
+    fn from(color: Color) -> Self {
+        color.to_hex().into()
+    }
+}
+
+#[cfg(feature = "gpui")]
+impl From<Color> for Hsla {
+    fn from(color: Color) -> Self {
+        let channel = |value: u8| f32::from(value) / 255.0;
+        Rgba {
+            r: channel(color.r),
+            g: channel(color.g),
+            b: channel(color.b),
+            a: channel(color.a),
+        }
+        .into()
+    }
+}
+
+impl Serialize for Color {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Color {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        Self::from_hex(&value).map_err(de::Error::custom)
+    }
+}
+
+/// Color expressed in the OKLCH perceptual space.
+///
+/// Lightness runs from 0.0 (black) to 1.0 (white) and hue is in degrees.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct Oklch {
+    pub(crate) l: f32,
+    pub(crate) c: f32,
+    pub(crate) h: f32,
+}
+
+impl Oklch {
+    /// Converts a color into OKLCH, ignoring its alpha.
+    pub(crate) fn from_color(color: Color) -> Self {
+        let [red, green, blue] = color
+            .channels()
+            .map(|channel| srgb_to_linear(f32::from(channel) / 255.0));
+
+        let long = (0.412_221_46 * red + 0.536_332_55 * green + 0.051_445_995 * blue).cbrt();
+        let medium = (0.211_903_5 * red + 0.680_699_5 * green + 0.107_396_96 * blue).cbrt();
+        let short = (0.088_302_46 * red + 0.281_718_85 * green + 0.629_978_7 * blue).cbrt();
+
+        let lightness = 0.210_454_26 * long + 0.793_617_8 * medium - 0.004_072_047 * short;
+        let green_red = 1.977_998_5 * long - 2.428_592_2 * medium + 0.450_593_7 * short;
+        let blue_yellow = 0.025_904_037 * long + 0.782_771_77 * medium - 0.808_675_77 * short;
+
+        Self {
+            l: lightness,
+            c: green_red.hypot(blue_yellow),
+            h: blue_yellow.atan2(green_red).to_degrees().rem_euclid(360.0),
+        }
+    }
+
+    /// Returns the same hue and chroma at a different lightness.
+    pub(crate) fn with_lightness(self, l: f32) -> Self {
+        Self {
+            l: l.clamp(0.0, 1.0),
+            ..self
+        }
+    }
+
+    /// Converts back to an opaque sRGB color, reducing chroma until it fits
+    /// the sRGB gamut so lightness and hue are preserved.
+    pub(crate) fn to_color(self) -> Color {
+        if let Some(rgb) = self.linear_rgb() {
+            return encode(rgb);
+        }
+
+        let (mut low, mut high) = (0.0, self.c);
+        for _ in 0..SEARCH_STEPS {
+            let mid = f32::midpoint(low, high);
+            if (Self { c: mid, ..self }).linear_rgb().is_some() {
+                low = mid;
+            } else {
+                high = mid;
+            }
+        }
+        let fitted = Self { c: low, ..self };
+        encode(
+            fitted
+                .linear_rgb()
+                .unwrap_or_else(|| fitted.linear_rgb_unclamped()),
+        )
+    }
+
+    fn linear_rgb(self) -> Option<[f32; 3]> {
+        const EPSILON: f32 = 1e-4;
+        let rgb = self.linear_rgb_unclamped();
+        rgb.iter()
+            .all(|channel| (-EPSILON..=1.0 + EPSILON).contains(channel))
+            .then_some(rgb)
+    }
+
+    fn linear_rgb_unclamped(self) -> [f32; 3] {
+        let (sin, cos) = self.h.to_radians().sin_cos();
+        let (green_red, blue_yellow) = (self.c * cos, self.c * sin);
+
+        let long = (self.l + 0.396_337_78 * green_red + 0.215_803_76 * blue_yellow).powi(3);
+        let medium = (self.l - 0.105_561_346 * green_red - 0.063_854_17 * blue_yellow).powi(3);
+        let short = (self.l - 0.089_484_18 * green_red - 1.291_485_5 * blue_yellow).powi(3);
+
+        [
+            4.076_741_7 * long - 3.307_711_6 * medium + 0.230_969_94 * short,
+            -1.268_438 * long + 2.609_757_4 * medium - 0.341_319_38 * short,
+            -0.004_196_086_3 * long - 0.703_418_6 * medium + 1.707_614_7 * short,
+        ]
+    }
+}
+
+fn encode(rgb: [f32; 3]) -> Color {
+    Color::from_channels(rgb.map(|channel| {
+        let channel = channel.clamp(0.0, 1.0);
+        let encoded = if channel <= 0.003_130_8 {
+            channel * 12.92
+        } else {
+            1.055 * channel.powf(1.0 / 2.4) - 0.055
+        };
+        // The clamp above keeps the value within 0..=255.
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let byte = (encoded * 255.0).round() as u8;
+        byte
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hex_parsing_supports_alpha() {
+        let color = Color::from_hex("#2563EB80").unwrap();
+        assert_eq!(color, Color::rgba(0x25, 0x63, 0xeb, 0x80));
+        assert_eq!(color.to_hex(), "#2563eb80");
+        assert_eq!(Color::from_hex("#2563eb").unwrap().to_hex(), "#2563eb");
+        assert_eq!(Color::hex("#2563eb80"), color);
+        for invalid in ["2563eb", "#fff", "#2563eg", "#2563eb8"] {
+            assert!(Color::from_hex(invalid).is_err(), "{invalid}");
+        }
+
+        let json = serde_json::to_string(&color).unwrap();
+        assert_eq!(json, "\"#2563eb80\"");
+        assert_eq!(serde_json::from_str::<Color>(&json).unwrap(), color);
+    }
+
+    #[test]
+    fn mix_interpolates_every_channel() {
+        let from = Color::rgba(0, 100, 200, 0);
+        let to = Color::rgba(200, 100, 0, 255);
+        assert_eq!(from.mix(to, 0.0), from);
+        assert_eq!(from.mix(to, 1.0), to);
+        assert_eq!(from.mix(to, 0.5), Color::rgba(100, 100, 100, 128));
+        assert_eq!(from.mix(to, 2.0), to);
+    }
+
+    #[test]
+    fn oklch_round_trips_srgb() {
+        for hex in [
+            "#2563eb", "#f8fafc", "#0ea5e9", "#000000", "#ffffff", "#f59e0b",
+        ] {
+            let color = Color::from_hex(hex).unwrap();
+            assert_eq!(Oklch::from_color(color).to_color(), color);
+        }
+    }
+
+    #[test]
+    fn out_of_gamut_colors_keep_lightness() {
+        let vivid = Oklch::from_color(Color::hex("#0ea5e9")).with_lightness(0.95);
+        let fitted = Oklch::from_color(vivid.to_color());
+        assert!((fitted.l - 0.95).abs() < 0.01);
+        assert!(fitted.c <= vivid.c);
+    }
+}
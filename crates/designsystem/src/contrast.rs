@@ -0,0 +1,374 @@
+//! WCAG 2.x contrast auditing for design token palettes.
+
+use serde::Serialize;
+use thiserror::Error;
+
+use crate::{
+    color::{Color, Oklch, SEARCH_STEPS},
+    tokens::{ColorPalette, DesignTokens},
+};
+
+/// WCAG conformance levels for text contrast.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+pub enum WcagLevel {
+    /// Minimum contrast: 4.5:1 for body text, 3:1 for large text.
+    AA,
+    /// Enhanced contrast: 7:1 for body text, 4.5:1 for large text.
+    AAA,
+}
+
+impl WcagLevel {
+    /// Returns the minimum contrast ratio required for the level.
+    #[must_use]
+    pub const fn min_ratio(self, large_text: bool) -> f32 {
+        match (self, large_text) {
+            (WcagLevel::AA, false) | (WcagLevel::AAA, true) => 4.5,
+            (WcagLevel::AA, true) => 3.0,
+            (WcagLevel::AAA, false) => 7.0,
+        }
+    }
+}
+
+/// Error returned when a palette cannot be corrected.
+#[derive(Debug, Error)]
+pub enum ContrastError {
+    /// Raised when a correction target is not a valid WCAG ratio.
+    #[error("target contrast ratio {0} must be between 1 and 21")]
+    InvalidTarget(f32),
+}
+
+/// Contrast measurement for one foreground/background pairing.
+#[derive(Debug, Clone, Serialize)]
+pub struct ContrastCheck {
+    /// Palette field used as the foreground, such as `on_primary`.
+    pub foreground: &'static str,
+    /// Palette field used as the background, such as `primary`.
+    pub background: &'static str,
+    /// Foreground color value.
+    pub foreground_color: Color,
+    /// Background color value.
+    pub background_color: Color,
+    /// Contrast ratio between the two colors, from 1.0 to 21.0.
+    pub ratio: f32,
+    /// Whether body text passes WCAG AA (4.5:1).
+    pub aa: bool,
+    /// Whether body text passes WCAG AAA (7:1).
+    pub aaa: bool,
+}
+
+impl ContrastCheck {
+    /// Returns `true` when the pairing meets `level` for body text.
+    #[must_use]
+    pub fn passes(&self, level: WcagLevel) -> bool {
+        self.ratio >= level.min_ratio(false)
+    }
+}
+
+/// Contrast results for every foreground/background pairing in a palette.
+#[derive(Debug, Clone, Serialize)]
+pub struct ContrastReport {
+    /// Individual pairings in palette order.
+    pub checks: Vec<ContrastCheck>,
+}
+
+impl ContrastReport {
+    /// Returns the pairings that fall short of `level`.
+    pub fn failures(&self, level: WcagLevel) -> impl Iterator<Item = &ContrastCheck> {
+        self.checks.iter().filter(move |check| !check.passes(level))
+    }
+
+    /// Returns `true` when every pairing meets `level`.
+    #[must_use]
+    pub fn passes(&self, level: WcagLevel) -> bool {
+        self.failures(level).next().is_none()
+    }
+
+    /// Returns the pairing with the lowest contrast ratio.
+    #[must_use]
+    pub fn weakest(&self) -> Option<&ContrastCheck> {
+        self.checks
+            .iter()
+            .min_by(|a, b| a.ratio.total_cmp(&b.ratio))
+    }
+}
+
+/// Audits every foreground/background pairing in the token palette.
+///
+/// Foreground tokens (`on_*` and the status colors) are paired with the
+/// surfaces they are rendered on across the workspace components.
+#[must_use]
+pub fn audit_contrast(tokens: &DesignTokens) -> ContrastReport {
+    let colors = &tokens.colors;
+    let pairings = [
+        ("on_primary", colors.on_primary, "primary", colors.primary),
+        ("on_accent", colors.on_accent, "accent", colors.accent),
+        ("on_muted", colors.on_muted, "muted", colors.muted),
+        ("on_muted", colors.on_muted, "background", colors.background),
+        ("on_muted", colors.on_muted, "surface", colors.surface),
+        ("on_sidebar", colors.on_sidebar, "sidebar", colors.sidebar),
+        ("success", colors.success, "background", colors.background),
+        ("warning", colors.warning, "background", colors.background),
+        ("danger", colors.danger, "background", colors.background),
+    ];
+
+    let checks = pairings
+        .into_iter()
+        .map(
+            |(foreground, foreground_color, background, background_color)| {
+                let ratio = contrast_ratio(foreground_color, background_color);
+                ContrastCheck {
+                    foreground,
+                    background,
+                    foreground_color,
+                    background_color,
+                    ratio,
+                    aa: ratio >= WcagLevel::AA.min_ratio(false),
+                    aaa: ratio >= WcagLevel::AAA.min_ratio(false),
+                }
+            },
+        )
+        .collect();
+
+    ContrastReport { checks }
+}
+
+/// Foreground color changed by [`correct_contrast`].
+#[derive(Debug, Clone, Serialize)]
+pub struct ColorAdjustment {
+    /// Palette field that was adjusted, such as `on_accent`.
+    pub token: &'static str,
+    /// Color before the correction.
+    pub before: Color,
+    /// Color after the correction.
+    pub after: Color,
+    /// Lowest contrast ratio against the token's backgrounds before the
+    /// correction.
+    pub ratio_before: f32,
+    /// Lowest contrast ratio against the token's backgrounds after the
+    /// correction.
+    pub ratio_after: f32,
+}
+
+/// Palette returned by [`correct_contrast`] together with the changes made.
+#[derive(Debug, Clone, Serialize)]
+pub struct ContrastCorrection {
+    /// Palette with every adjustable foreground nudged toward the target.
+    pub palette: ColorPalette,
+    /// Foreground colors that changed, in palette order.
+    pub adjustments: Vec<ColorAdjustment>,
+    /// Contrast ratio the correction aimed for.
+    pub target_ratio: f32,
+}
+
+impl ContrastCorrection {
+    /// Returns the adjustments that still fall short of the target, which
+    /// happens when no lightness of the original hue can reach it.
+    pub fn unresolved(&self) -> impl Iterator<Item = &ColorAdjustment> {
+        self.adjustments
+            .iter()
+            .filter(|adjustment| adjustment.ratio_after < self.target_ratio)
+    }
+}
+
+/// Nudges the `on_*` foreground colors of `palette` until each one reaches
+/// `target_ratio` against the surfaces it is rendered on.
+///
+/// Colors are adjusted in OKLCH, changing lightness as little as possible
+/// while keeping the original hue. Backgrounds and status colors are left
+/// untouched. Foregrounds that cannot reach the target end up at the
+/// strongest contrast available and are listed by
+/// [`ContrastCorrection::unresolved`].
+///
+/// # Errors
+///
+/// Returns [`ContrastError::InvalidTarget`] when `target_ratio` is outside
+/// `1.0..=21.0`.
+pub fn correct_contrast(
+    palette: &ColorPalette,
+    target_ratio: f32,
+) -> Result<ContrastCorrection, ContrastError> {
+    if !(1.0..=21.0).contains(&target_ratio) {
+        return Err(ContrastError::InvalidTarget(target_ratio));
+    }
+
+    let mut corrected = palette.clone();
+    let mut adjustments = Vec::new();
+    let foregrounds: [(&'static str, &mut Color, &[Color]); 4] = [
+        ("on_primary", &mut corrected.on_primary, &[palette.primary]),
+        ("on_accent", &mut corrected.on_accent, &[palette.accent]),
+        (
+            "on_muted",
+            &mut corrected.on_muted,
+            &[palette.muted, palette.background, palette.surface],
+        ),
+        ("on_sidebar", &mut corrected.on_sidebar, &[palette.sidebar]),
+    ];
+    for (token, value, backgrounds) in foregrounds {
+        let before = *value;
+        let ratio_before = min_ratio(before, backgrounds);
+        if ratio_before >= target_ratio {
+            continue;
+        }
+
+        let (after, ratio_after) = nudge_lightness(before, backgrounds, target_ratio);
+        *value = after;
+        adjustments.push(ColorAdjustment {
+            token,
+            before,
+            after,
+            ratio_before,
+            ratio_after,
+        });
+    }
+
+    Ok(ContrastCorrection {
+        palette: corrected,
+        adjustments,
+        target_ratio,
+    })
+}
+
+fn min_ratio(foreground: Color, backgrounds: &[Color]) -> f32 {
+    let foreground = relative_luminance(foreground);
+    backgrounds
+        .iter()
+        .map(|background| ratio_from_luminance(foreground, relative_luminance(*background)))
+        .fold(f32::INFINITY, f32::min)
+}
+
+/// Searches darker and lighter variants of `foreground` for the smallest
+/// lightness change that reaches `target`, falling back to the variant with
+/// the best contrast when neither direction gets there.
+fn nudge_lightness(foreground: Color, backgrounds: &[Color], target: f32) -> (Color, f32) {
+    let base = Oklch::from_color(foreground);
+    let [darker, lighter] = [0.0, 1.0].map(|extreme| {
+        let at = |t: f32| {
+            let rgb = base
+                .with_lightness(base.l + (extreme - base.l) * t)
+                .to_color();
+            (rgb, min_ratio(rgb, backgrounds))
+        };
+
+        let (mut rgb, mut ratio) = at(1.0);
+        let mut distance = (extreme - base.l).abs();
+        if ratio >= target {
+            let (mut low, mut high) = (0.0, 1.0);
+            for _ in 0..SEARCH_STEPS {
+                let mid = f32::midpoint(low, high);
+                let (mid_rgb, mid_ratio) = at(mid);
+                if mid_ratio >= target {
+                    high = mid;
+                    (rgb, ratio) = (mid_rgb, mid_ratio);
+                } else {
+                    low = mid;
+                }
+            }
+            distance *= high;
+        }
+        (distance, rgb, ratio)
+    });
+
+    let (_, rgb, ratio) = match (darker.2 >= target, lighter.2 >= target) {
+        (true, true) if darker.0 <= lighter.0 => darker,
+        (true, false) => darker,
+        (false, false) if darker.2 >= lighter.2 => darker,
+        _ => lighter,
+    };
+    (rgb, ratio)
+}
+
+/// Returns the WCAG contrast ratio between two colors, from 1.0 to 21.0.
+///
+/// Alpha is ignored, so translucent colors are measured as if opaque.
+#[must_use]
+pub fn contrast_ratio(foreground: Color, background: Color) -> f32 {
+    ratio_from_luminance(
+        relative_luminance(foreground),
+        relative_luminance(background),
+    )
+}
+
+/// Returns the WCAG relative luminance of a color, ignoring alpha.
+#[must_use]
+pub fn relative_luminance(color: Color) -> f32 {
+    let [r, g, b] = color
+        .channels()
+        .map(|channel| srgb_to_linear(f32::from(channel) / 255.0));
+    0.2126 * r + 0.7152 * g + 0.0722 * b
+}
+
+/// Converts a gamma-encoded sRGB channel in `0.0..=1.0` to linear light.
+#[must_use]
+pub fn srgb_to_linear(value: f32) -> f32 {
+    if value <= 0.04045 {
+        value / 12.92
+    } else {
+        ((value + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+fn ratio_from_luminance(l1: f32, l2: f32) -> f32 {
+    let (lighter, darker) = if l1 > l2 { (l1, l2) } else { (l2, l1) };
+    (lighter + 0.05) / (darker + 0.05)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tokens::{dark_tokens, high_contrast_tokens, light_tokens};
+
+    #[test]
+    fn black_on_white_is_maximum_contrast() {
+        let ratio = contrast_ratio(Color::hex("#000000"), Color::hex("#ffffff"));
+        assert!((ratio - 21.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn audit_covers_every_pairing() {
+        let report = audit_contrast(&dark_tokens());
+        assert_eq!(report.checks.len(), 9);
+        let primary = &report.checks[0];
+        assert_eq!(
+            (primary.foreground, primary.background),
+            ("on_primary", "primary")
+        );
+        assert_eq!(primary.aa, primary.passes(WcagLevel::AA));
+        assert!(report.weakest().unwrap().ratio <= primary.ratio);
+    }
+
+    #[test]
+    fn correction_lifts_failing_foregrounds() {
+        let palette = light_tokens().colors;
+        let correction = correct_contrast(&palette, 4.5).unwrap();
+        assert!(correction.unresolved().next().is_none());
+        assert!(correction
+            .adjustments
+            .iter()
+            .any(|adjustment| adjustment.token == "on_accent"));
+        for adjustment in &correction.adjustments {
+            assert!(adjustment.ratio_before < 4.5);
+            assert!(adjustment.ratio_after >= 4.5);
+        }
+
+        let corrected = &correction.palette;
+        assert!(contrast_ratio(corrected.on_accent, corrected.accent) >= 4.5);
+        assert_eq!(corrected.accent, palette.accent);
+        assert_eq!(corrected.danger, palette.danger);
+    }
+
+    #[test]
+    fn passing_palettes_are_left_alone() {
+        let palette = high_contrast_tokens().colors;
+        let correction = correct_contrast(&palette, 4.5).unwrap();
+        assert!(correction.adjustments.is_empty());
+        assert_eq!(correction.palette.on_muted, palette.on_muted);
+    }
+
+    #[test]
+    fn correction_rejects_impossible_targets() {
+        assert!(matches!(
+            correct_contrast(&dark_tokens().colors, 22.0),
+            Err(ContrastError::InvalidTarget(_))
+        ));
+    }
+}
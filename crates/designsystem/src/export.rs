@@ -0,0 +1,311 @@
+//! Exporters that publish design tokens to web tooling.
+
+use std::fmt::Write as _;
+
+use serde_json::{json, Map, Value};
+
+use crate::{
+    color::Color,
+    tokens::{DesignTokens, ElevationLevel},
+};
+
+/// Output formats supported by [`export_tokens`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenFormat {
+    /// CSS custom properties, e.g. `--color-primary: #2563eb;`.
+    Css,
+    /// W3C Design Tokens Community Group JSON.
+    Dtcg,
+    /// Tokens Studio for Figma JSON, with every token in one `global` set.
+    Figma,
+    /// A `tailwind.config.js` module extending the default theme.
+    Tailwind,
+}
+
+/// Serializes `tokens` in the requested format.
+///
+/// CSS output is scoped to `selector`, typically `:root` or
+/// `[data-theme="dark"]`; other formats ignore it.
+#[must_use]
+pub fn export_tokens(tokens: &DesignTokens, format: TokenFormat, selector: &str) -> String {
+    match format {
+        TokenFormat::Css => to_css_variables(tokens, selector),
+        TokenFormat::Dtcg => to_dtcg_json(tokens),
+        TokenFormat::Figma => to_figma_tokens(tokens),
+        TokenFormat::Tailwind => to_tailwind_config(tokens),
+    }
+}
+
+/// Renders the tokens as CSS custom properties inside `selector`.
+#[must_use]
+pub fn to_css_variables(tokens: &DesignTokens, selector: &str) -> String {
+    let mut css = format!("{selector} {{\n");
+    let mut property = |name: String, value: String| {
+        let _ = writeln!(css, "  --{name}: {value};");
+    };
+
+    for (name, color) in tokens.colors.entries() {
+        property(format!("color-{}", kebab(name)), color.to_hex());
+    }
+    property(
+        "font-family".into(),
+        format!("\"{}\"", tokens.typography.font_family),
+    );
+    for (name, size) in tokens.typography.sizes() {
+        property(format!("font-size-{name}"), px(size));
+    }
+    for (name, value) in tokens.spacing.entries() {
+        property(format!("spacing-{name}"), px(value));
+    }
+    for (name, level) in tokens.elevations.entries() {
+        property(format!("elevation-{name}"), box_shadow(level));
+    }
+
+    css.push_str("}\n");
+    css
+}
+
+/// Renders the tokens as a W3C Design Tokens Community Group document.
+#[must_use]
+pub fn to_dtcg_json(tokens: &DesignTokens) -> String {
+    let token = |kind: &str, value: Value| json!({ "$type": kind, "$value": value });
+
+    let color: Map<String, Value> = tokens
+        .colors
+        .entries()
+        .into_iter()
+        .map(|(name, color)| (name.to_owned(), token("color", json!(color))))
+        .collect();
+    let size: Map<String, Value> = tokens
+        .typography
+        .sizes()
+        .into_iter()
+        .map(|(name, size)| (name.to_owned(), token("dimension", json!(px(size)))))
+        .collect();
+    let spacing: Map<String, Value> = tokens
+        .spacing
+        .entries()
+        .into_iter()
+        .map(|(name, value)| (name.to_owned(), token("dimension", json!(px(value)))))
+        .collect();
+    let elevation: Map<String, Value> = tokens
+        .elevations
+        .entries()
+        .into_iter()
+        .map(|(name, level)| {
+            let shadow = json!({
+                "color": shadow_hex(level.opacity),
+                "offsetX": "0px",
+                "offsetY": px(level.y_offset),
+                "blur": px(level.blur_radius),
+                "spread": px(level.spread_radius),
+            });
+            (name.to_owned(), token("shadow", shadow))
+        })
+        .collect();
+
+    let document = json!({
+        "color": color,
+        "font": {
+            "family": token("fontFamily", json!(tokens.typography.font_family)),
+            "size": size,
+        },
+        "spacing": spacing,
+        "elevation": elevation,
+    });
+    pretty(&document)
+}
+
+/// Name of the token set written by [`to_figma_tokens`], matching the set
+/// Tokens Studio creates for new files.
+const FIGMA_TOKEN_SET: &str = "global";
+
+/// Renders the tokens as a Tokens Studio for Figma document.
+///
+/// Tokens Studio predates the DTCG draft: tokens use `value`/`type` keys,
+/// types such as `fontSizes` and `boxShadow`, and live in a named token set
+/// listed in `$metadata`.
+#[must_use]
+pub fn to_figma_tokens(tokens: &DesignTokens) -> String {
+    let token = |kind: &str, value: Value| json!({ "value": value, "type": kind });
+
+    let color: Map<String, Value> = tokens
+        .colors
+        .entries()
+        .into_iter()
+        .map(|(name, color)| (name.to_owned(), token("color", json!(color))))
+        .collect();
+    let size: Map<String, Value> = tokens
+        .typography
+        .sizes()
+        .into_iter()
+        .map(|(name, size)| (name.to_owned(), token("fontSizes", json!(px(size)))))
+        .collect();
+    let spacing: Map<String, Value> = tokens
+        .spacing
+        .entries()
+        .into_iter()
+        .map(|(name, value)| (name.to_owned(), token("spacing", json!(px(value)))))
+        .collect();
+    let elevation: Map<String, Value> = tokens
+        .elevations
+        .entries()
+        .into_iter()
+        .map(|(name, level)| {
+            let shadow = json!({
+                "x": "0",
+                "y": level.y_offset.to_string(),
+                "blur": level.blur_radius.to_string(),
+                "spread": level.spread_radius.to_string(),
+                "color": shadow_hex(level.opacity),
+                "type": "dropShadow",
+            });
+            (name.to_owned(), token("boxShadow", shadow))
+        })
+        .collect();
+
+    let document = json!({
+        FIGMA_TOKEN_SET: {
+            "color": color,
+            "font": {
+                "family": token("fontFamilies", json!(tokens.typography.font_family)),
+                "size": size,
+            },
+            "spacing": spacing,
+            "elevation": elevation,
+        },
+        "$themes": [],
+        "$metadata": { "tokenSetOrder": [FIGMA_TOKEN_SET] },
+    });
+    pretty(&document)
+}
+
+/// Renders the tokens as a Tailwind config that extends the default theme.
+#[must_use]
+pub fn to_tailwind_config(tokens: &DesignTokens) -> String {
+    let colors: Map<String, Value> = tokens
+        .colors
+        .entries()
+        .into_iter()
+        .map(|(name, color)| (kebab(name), json!(color)))
+        .collect();
+    let font_size: Map<String, Value> = tokens
+        .typography
+        .sizes()
+        .into_iter()
+        .map(|(name, size)| (name.to_owned(), json!(px(size))))
+        .collect();
+    let spacing: Map<String, Value> = tokens
+        .spacing
+        .entries()
+        .into_iter()
+        .map(|(name, value)| (name.to_owned(), json!(px(value))))
+        .collect();
+    let box_shadow: Map<String, Value> = tokens
+        .elevations
+        .entries()
+        .into_iter()
+        .map(|(name, level)| (name.to_owned(), json!(box_shadow(level))))
+        .collect();
+
+    let config = json!({
+        "theme": {
+            "extend": {
+                "colors": colors,
+                "fontFamily": { "sans": [tokens.typography.font_family, "sans-serif"] },
+                "fontSize": font_size,
+                "spacing": spacing,
+                "boxShadow": box_shadow,
+            }
+        }
+    });
+    format!(
+        "/** @type {{import('tailwindcss').Config}} */\nmodule.exports = {};\n",
+        pretty(&config).trim_end()
+    )
+}
+
+fn pretty(value: &Value) -> String {
+    let mut output = serde_json::to_string_pretty(value).unwrap_or_default();
+    output.push('\n');
+    output
+}
+
+fn kebab(name: &str) -> String {
+    name.replace('_', "-")
+}
+
+fn px(value: f32) -> String {
+    format!("{value}px")
+}
+
+fn box_shadow(level: ElevationLevel) -> String {
+    if level.opacity <= 0.0 {
+        return "none".into();
+    }
+    format!(
+        "0 {} {} {} rgba(0, 0, 0, {})",
+        px(level.y_offset),
+        px(level.blur_radius),
+        px(level.spread_radius),
+        level.opacity
+    )
+}
+
+/// Black shadow color with `opacity` encoded as `#rrggbbaa`.
+fn shadow_hex(opacity: f32) -> String {
+    // Opacity is validated to 0..=1, so the scaled value fits in a byte.
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let alpha = (opacity.clamp(0.0, 1.0) * 255.0).round() as u8;
+    Color::rgba(0, 0, 0, alpha).to_hex()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tokens::{dark_tokens, light_tokens};
+
+    #[test]
+    fn css_export_lists_every_token() {
+        let css = to_css_variables(&light_tokens(), ":root");
+        assert!(css.starts_with(":root {\n"));
+        assert!(css.contains("  --color-on-primary: #f8fafc;\n"));
+        assert!(css.contains("  --font-family: \"Inter\";\n"));
+        assert!(css.contains("  --spacing-md: 12px;\n"));
+        assert!(css.contains("  --elevation-flat: none;\n"));
+    }
+
+    #[test]
+    fn dtcg_export_uses_typed_tokens() {
+        let document: Value = serde_json::from_str(&to_dtcg_json(&dark_tokens())).unwrap();
+        assert_eq!(document["color"]["primary"]["$type"], "color");
+        assert_eq!(document["color"]["primary"]["$value"], "#60a5fa");
+        assert_eq!(document["spacing"]["lg"]["$value"], "16px");
+        assert_eq!(
+            document["elevation"]["raised"]["$value"]["color"],
+            "#0000001f"
+        );
+    }
+
+    #[test]
+    fn figma_export_uses_a_tokens_studio_set() {
+        let document: Value =
+            serde_json::from_str(&export_tokens(&dark_tokens(), TokenFormat::Figma, ":root"))
+                .unwrap();
+        assert_eq!(document["$metadata"]["tokenSetOrder"][0], "global");
+        let set = &document["global"];
+        assert_eq!(set["color"]["primary"]["type"], "color");
+        assert_eq!(set["color"]["primary"]["value"], "#60a5fa");
+        assert_eq!(set["font"]["size"]["body"]["type"], "fontSizes");
+        assert_eq!(set["spacing"]["lg"]["value"], "16px");
+        assert_eq!(set["elevation"]["raised"]["type"], "boxShadow");
+        assert_eq!(set["elevation"]["raised"]["value"]["color"], "#0000001f");
+    }
+
+    #[test]
+    fn tailwind_export_is_a_commonjs_module() {
+        let config = to_tailwind_config(&light_tokens());
+        assert!(config.contains("module.exports = {"));
+        assert!(config.contains("\"surface-border\": \"#cbd5f5\""));
+    }
+}
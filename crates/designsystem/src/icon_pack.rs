@@ -0,0 +1,337 @@
+//! Icon packs loaded from disk at runtime and served next to the embedded
+//! icons.
+
+use std::{
+    borrow::Cow,
+    collections::BTreeMap,
+    fs, io,
+    path::{Path, PathBuf},
+    sync::{Arc, RwLock},
+};
+
+use gpui::{AssetSource, SharedString};
+use thiserror::Error;
+
+use crate::{
+    icons::IconAssetSource,
+    svg::{optimize_svg, SvgError, SvgOptions},
+};
+
+/// Directory the embedded icons are served from; packs live in
+/// subdirectories named after their prefix.
+const ICONS_ROOT: &str = "designsystem/icons";
+
+/// Errors raised while loading an icon pack.
+#[derive(Debug, Error)]
+pub enum IconPackError {
+    /// Raised when a pack prefix is not a lowercase slug.
+    #[error("invalid icon pack prefix '{0}': use lowercase letters, digits and dashes")]
+    InvalidPrefix(String),
+    /// Raised when a pack directory or file cannot be read.
+    #[error("failed to read icon pack '{}': {source}", path.display())]
+    Io {
+        /// Path that failed to load.
+        path: PathBuf,
+        /// Underlying IO error.
+        #[source]
+        source: io::Error,
+    },
+    /// Raised when an SVG in the pack fails validation.
+    #[error("invalid icon '{}': {source}", path.display())]
+    InvalidSvg {
+        /// File that failed validation.
+        path: PathBuf,
+        /// Validation error.
+        #[source]
+        source: SvgError,
+    },
+}
+
+/// Validated, minified SVGs of one pack, keyed by file name.
+type Pack = BTreeMap<String, Arc<[u8]>>;
+
+/// Asset source serving the embedded icons plus icon packs loaded from
+/// directories at runtime.
+///
+/// A pack loaded under the prefix `acme` serves `dir/rocket.svg` as
+/// `designsystem/icons/acme/rocket.svg`, next to the embedded
+/// `designsystem/icons/*.svg`, so plugins and deployments can add icons
+/// without rebuilding. Clones share the loaded packs, so a host can keep one
+/// handle and pass another to `Application::with_assets`.
+///
+/// ```no_run
+/// use designsystem::IconPackSource;
+/// use gpui::Application;
+///
+/// let icons = IconPackSource::new();
+/// icons.load_pack("acme", "/opt/acme/icons").expect("valid icon pack");
+/// let app = Application::new().with_assets(icons.clone());
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct IconPackSource {
+    packs: Arc<RwLock<BTreeMap<String, Pack>>>,
+}
+
+impl IconPackSource {
+    /// Creates a source that serves only the embedded icons until packs are
+    /// loaded.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Loads every `.svg` in `dir` as the pack `prefix`, replacing a pack
+    /// previously loaded under the same prefix, and returns the number of
+    /// icons.
+    ///
+    /// Files are validated and minified like the embedded icons: artwork
+    /// named `*.duotone.svg` may paint fixed colors, everything else must use
+    /// `currentColor`. The pack is only replaced when every file is valid.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`IconPackError::InvalidPrefix`] when `prefix` is not a
+    /// lowercase slug, [`IconPackError::Io`] when the directory cannot be
+    /// read, and [`IconPackError::InvalidSvg`] for the first file that fails
+    /// validation.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the icon pack lock was poisoned.
+    pub fn load_pack(&self, prefix: &str, dir: impl AsRef<Path>) -> Result<usize, IconPackError> {
+        let valid = !prefix.is_empty()
+            && prefix
+                .chars()
+                .all(|ch| ch.is_ascii_lowercase() || ch.is_ascii_digit() || ch == '-');
+        if !valid {
+            return Err(IconPackError::InvalidPrefix(prefix.to_owned()));
+        }
+
+        let dir = dir.as_ref();
+        let io_error = |path: &Path| {
+            let path = path.to_path_buf();
+            move |source| IconPackError::Io { path, source }
+        };
+        let mut pack = Pack::new();
+        for entry in fs::read_dir(dir).map_err(io_error(dir))? {
+            let path = entry.map_err(io_error(dir))?.path();
+            if path.extension().and_then(|ext| ext.to_str()) != Some("svg") {
+                continue;
+            }
+            let Some(file_name) = path.file_name().and_then(|name| name.to_str()) else {
+                continue;
+            };
+            let source = fs::read_to_string(&path).map_err(io_error(&path))?;
+            let options = if file_name.contains(".duotone") {
+                SvgOptions::MULTICOLOR
+            } else {
+                SvgOptions::MONOCHROME
+            };
+            let optimized =
+                optimize_svg(&source, options).map_err(|source| IconPackError::InvalidSvg {
+                    path: path.clone(),
+                    source,
+                })?;
+            pack.insert(file_name.to_owned(), optimized.svg.into_bytes().into());
+        }
+
+        let count = pack.len();
+        self.packs
+            .write()
+            .expect("icon pack lock poisoned")
+            .insert(prefix.to_owned(), pack);
+        Ok(count)
+    }
+
+    /// Loads each subdirectory of `dir` as a pack named after it and returns
+    /// the prefixes loaded.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error from [`IconPackSource::load_pack`]; packs
+    /// loaded before it stay loaded.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the icon pack lock was poisoned.
+    pub fn load_dir(&self, dir: impl AsRef<Path>) -> Result<Vec<String>, IconPackError> {
+        let dir = dir.as_ref();
+        let io_error = |source| IconPackError::Io {
+            path: dir.to_path_buf(),
+            source,
+        };
+        let mut subdirectories = Vec::new();
+        for entry in fs::read_dir(dir).map_err(io_error)? {
+            let path = entry.map_err(io_error)?.path();
+            if path.is_dir() {
+                subdirectories.push(path);
+            }
+        }
+        subdirectories.sort();
+
+        let mut prefixes = Vec::new();
+        for path in subdirectories {
+            let prefix = path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .unwrap_or_default()
+                .to_owned();
+            self.load_pack(&prefix, &path)?;
+            prefixes.push(prefix);
+        }
+        Ok(prefixes)
+    }
+
+    /// Removes the pack loaded under `prefix`, returning whether one was
+    /// loaded.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the icon pack lock was poisoned.
+    #[must_use]
+    pub fn unload_pack(&self, prefix: &str) -> bool {
+        self.packs
+            .write()
+            .expect("icon pack lock poisoned")
+            .remove(prefix)
+            .is_some()
+    }
+
+    /// Returns the prefixes of the loaded packs in alphabetical order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the icon pack lock was poisoned.
+    #[must_use]
+    pub fn packs(&self) -> Vec<String> {
+        self.packs
+            .read()
+            .expect("icon pack lock poisoned")
+            .keys()
+            .cloned()
+            .collect()
+    }
+
+    /// Returns the asset paths of every icon in the pack `prefix`, empty
+    /// when no such pack is loaded.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the icon pack lock was poisoned.
+    #[must_use]
+    pub fn pack_icons(&self, prefix: &str) -> Vec<SharedString> {
+        self.packs
+            .read()
+            .expect("icon pack lock poisoned")
+            .get(prefix)
+            .map(|pack| {
+                pack.keys()
+                    .map(|file_name| format!("{ICONS_ROOT}/{prefix}/{file_name}").into())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+}
+
+impl AssetSource for IconPackSource {
+    fn load(&self, path: &str) -> gpui::Result<Option<Cow<'static, [u8]>>> {
+        if let Some(embedded) = IconAssetSource.load(path)? {
+            return Ok(Some(embedded));
+        }
+        let Some((prefix, file_name)) = path
+            .strip_prefix(ICONS_ROOT)
+            .and_then(|rest| rest.strip_prefix('/'))
+            .and_then(|rest| rest.split_once('/'))
+        else {
+            return Ok(None);
+        };
+        Ok(self
+            .packs
+            .read()
+            .expect("icon pack lock poisoned")
+            .get(prefix)
+            .and_then(|pack| pack.get(file_name))
+            .map(|svg| Cow::Owned(svg.to_vec())))
+    }
+
+    fn list(&self, path: &str) -> gpui::Result<Vec<SharedString>> {
+        let mut entries = IconAssetSource.list(path)?;
+        if path == ICONS_ROOT {
+            entries.extend(
+                self.packs()
+                    .into_iter()
+                    .map(|prefix| format!("{ICONS_ROOT}/{prefix}").into()),
+            );
+        } else if let Some(prefix) = path
+            .strip_prefix(ICONS_ROOT)
+            .and_then(|rest| rest.strip_prefix('/'))
+        {
+            entries.extend(self.pack_icons(prefix));
+        }
+        Ok(entries)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::IconName;
+
+    const ROCKET: &str = "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"0 0 24 24\">\
+                          <!-- exported --><path stroke=\"currentColor\" d=\"M4 20L20 4\"/></svg>";
+
+    #[test]
+    fn packs_are_served_under_their_prefix() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("rocket.svg"), ROCKET).unwrap();
+        fs::write(dir.path().join("README.md"), "not an icon").unwrap();
+
+        let source = IconPackSource::new();
+        assert_eq!(source.load_pack("acme", dir.path()).unwrap(), 1);
+        let rocket = source
+            .load("designsystem/icons/acme/rocket.svg")
+            .unwrap()
+            .expect("pack icon is served");
+        assert!(!String::from_utf8_lossy(&rocket).contains("exported"));
+        assert!(source
+            .load(IconName::Search.asset_path())
+            .unwrap()
+            .is_some());
+        assert!(source
+            .list("designsystem/icons")
+            .unwrap()
+            .contains(&SharedString::from("designsystem/icons/acme")));
+        assert_eq!(
+            source.pack_icons("acme"),
+            vec![SharedString::from("designsystem/icons/acme/rocket.svg")]
+        );
+
+        assert!(source.unload_pack("acme"));
+        assert!(source
+            .load("designsystem/icons/acme/rocket.svg")
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn invalid_packs_are_rejected_whole() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("rocket.svg"), ROCKET).unwrap();
+        fs::write(
+            dir.path().join("logo.svg"),
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"0 0 24 24\" fill=\"#f00\"/>",
+        )
+        .unwrap();
+
+        let source = IconPackSource::new();
+        assert!(matches!(
+            source.load_pack("acme", dir.path()),
+            Err(IconPackError::InvalidSvg { .. })
+        ));
+        assert!(source.packs().is_empty());
+        assert!(matches!(
+            source.load_pack("Acme Icons", dir.path()),
+            Err(IconPackError::InvalidPrefix(_))
+        ));
+    }
+}
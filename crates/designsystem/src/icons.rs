@@ -1,19 +1,32 @@
 //! Icon pipeline that converts SVG assets into an enum and runtime loader.
 
-use std::{borrow::Cow, collections::HashMap, sync::LazyLock};
+#[cfg(feature = "gpui")]
+use std::borrow::Cow;
+use std::{collections::HashMap, sync::LazyLock};
 
-use gpui::{AssetSource, Result, SharedString};
+#[cfg(feature = "gpui")]
+use gpui::{App, AssetSource, Global, Result, SharedString};
 
 include!(concat!(env!("OUT_DIR"), "/icon_data.rs"));
 
+#[cfg(feature = "gpui")]
 static ICON_SOURCE: LazyLock<HashMap<&'static str, &'static str>> = LazyLock::new(|| {
     let mut map = HashMap::new();
-    for icon in IconName::ALL {
-        map.insert(icon.asset_path(), icon.svg());
+    for artwork in ICON_ARTWORK {
+        map.insert(artwork.asset_path, artwork.svg);
     }
     map
 });
 
+static ARTWORK_BY_ICON: LazyLock<HashMap<IconName, Vec<&'static IconArtwork>>> =
+    LazyLock::new(|| {
+        let mut map: HashMap<_, Vec<_>> = HashMap::new();
+        for artwork in ICON_ARTWORK {
+            map.entry(artwork.icon).or_default().push(artwork);
+        }
+        map
+    });
+
 static ICON_BY_NAME: LazyLock<HashMap<&'static str, IconName>> = LazyLock::new(|| {
     let mut map = HashMap::new();
     for (name, icon) in ICON_NAMES {
@@ -22,10 +35,301 @@ static ICON_BY_NAME: LazyLock<HashMap<&'static str, IconName>> = LazyLock::new(|
     map
 });
 
+/// Drawing style of an icon's artwork.
+///
+/// Artwork files declare their style with a suffix, such as
+/// `search.filled.svg`; the plain `search.svg` is the outline artwork every
+/// icon has.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum IconStyle {
+    /// Stroked artwork, the style of every base icon.
+    #[default]
+    Outline,
+    /// Solid artwork for selected or emphasized states.
+    Filled,
+    /// Two-tone artwork, either translucent `currentColor` layers or fixed
+    /// brand colors; see [`IconVariant::is_multicolor`].
+    Duotone,
+}
+
+impl IconStyle {
+    /// Every style in declaration order.
+    pub const ALL: [Self; 3] = [Self::Outline, Self::Filled, Self::Duotone];
+
+    /// Returns the file name suffix used for the style.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Outline => "outline",
+            Self::Filled => "filled",
+            Self::Duotone => "duotone",
+        }
+    }
+
+    /// Styles tried in order when an icon has no artwork in `self`.
+    const fn fallbacks(self) -> &'static [Self] {
+        match self {
+            Self::Outline => &[Self::Outline],
+            Self::Filled => &[Self::Filled, Self::Outline],
+            Self::Duotone => &[Self::Duotone, Self::Filled, Self::Outline],
+        }
+    }
+}
+
+/// Descriptive data for an icon, read from the optional `<stem>.toml`
+/// sidecar next to its SVG.
+///
+/// ```toml
+/// label = "Search"
+/// category = "actions"
+/// tags = ["find", "lookup"]
+/// synonyms = ["magnifier", "magnifying glass"]
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IconMetadata {
+    /// Human readable name, also used as the accessible label. Derived from
+    /// the stem (`product-palette` becomes `Product palette`) when omitted.
+    pub label: &'static str,
+    /// Group pickers list the icon under; `general` when omitted.
+    pub category: &'static str,
+    /// Concepts the icon depicts or is used for.
+    pub tags: &'static [&'static str],
+    /// Other names people search for, such as `magnifier` for `search`.
+    pub synonyms: &'static [&'static str],
+}
+
+/// One embedded SVG file for an icon.
+#[derive(Debug)]
+pub(crate) struct IconArtwork {
+    pub(crate) icon: IconName,
+    pub(crate) style: IconStyle,
+    /// Pixel size the artwork was drawn for, or `None` when it is meant to
+    /// scale to any size.
+    pub(crate) size: Option<u16>,
+    pub(crate) asset_path: &'static str,
+    pub(crate) svg: &'static str,
+    /// Whether the SVG paints its own colors rather than `currentColor`.
+    pub(crate) multicolor: bool,
+}
+
+impl IconName {
+    /// Selects the artwork for `style`, falling back to filled and then
+    /// outline artwork when the icon does not ship that style.
+    ///
+    /// ```
+    /// use designsystem::{IconName, IconStyle};
+    ///
+    /// let icon = IconName::Search.variant(IconStyle::Filled).at_size(16);
+    /// assert!(icon.asset_path().starts_with("designsystem/icons/search"));
+    /// ```
+    #[must_use]
+    pub fn variant(self, style: IconStyle) -> IconVariant {
+        IconVariant {
+            name: self,
+            requested: style,
+            size: None,
+        }
+    }
+
+    /// Returns the styles this icon has artwork for.
+    pub fn styles(self) -> impl Iterator<Item = IconStyle> {
+        IconStyle::ALL
+            .into_iter()
+            .filter(move |style| self.artwork().iter().any(|artwork| artwork.style == *style))
+    }
+
+    /// Returns the localization key that overrides the icon's accessible
+    /// label: `icon.<stem>`, as in `icon.search`.
+    #[must_use]
+    pub fn label_key(self) -> String {
+        format!("icon.{}", self.stem())
+    }
+
+    /// Returns the text alternative for the icon: the message `translate`
+    /// finds for [`label_key`](Self::label_key), otherwise the label from
+    /// the icon's metadata.
+    ///
+    /// ```
+    /// use designsystem::IconName;
+    ///
+    /// let label = IconName::Search.accessible_label(|key| {
+    ///     (key == "icon.search").then(|| "Buscar".to_owned())
+    /// });
+    /// assert_eq!(label.as_ref(), "Buscar");
+    /// ```
+    #[cfg(feature = "gpui")]
+    pub fn accessible_label(self, translate: impl FnOnce(&str) -> Option<String>) -> SharedString {
+        translate(&self.label_key()).map_or_else(|| self.metadata().label.into(), Into::into)
+    }
+
+    fn artwork(self) -> &'static [&'static IconArtwork] {
+        ARTWORK_BY_ICON.get(&self).map_or(&[], Vec::as_slice)
+    }
+}
+
+/// Accessible labels for every icon in one locale.
+///
+/// Installed as a GPUI global so components read labels with
+/// [`ActiveIconLabels::icon_label`] without holding a localization handle.
+/// Hosts rebuild and reinstall it whenever the locale changes.
+#[cfg(feature = "gpui")]
+#[derive(Debug, Clone, Default)]
+pub struct IconLabels {
+    labels: HashMap<IconName, SharedString>,
+}
+
+#[cfg(feature = "gpui")]
+impl Global for IconLabels {}
+
+#[cfg(feature = "gpui")]
+impl IconLabels {
+    /// Resolves every icon's label through `translate`, which receives each
+    /// [`IconName::label_key`], such as
+    /// `|key| localization.translate(&locale, key)`.
+    #[must_use]
+    pub fn localized(translate: impl Fn(&str) -> Option<String>) -> Self {
+        Self {
+            labels: IconName::ALL
+                .iter()
+                .map(|icon| (*icon, icon.accessible_label(&translate)))
+                .collect(),
+        }
+    }
+
+    /// Returns the label for `icon`.
+    #[must_use]
+    pub fn label(&self, icon: IconName) -> SharedString {
+        self.labels
+            .get(&icon)
+            .cloned()
+            .unwrap_or_else(|| icon.metadata().label.into())
+    }
+
+    /// Makes these the labels [`ActiveIconLabels::icon_label`] returns,
+    /// replacing any installed before.
+    pub fn install(self, cx: &mut App) {
+        cx.set_global(self);
+    }
+}
+
+/// Read access to the installed [`IconLabels`].
+///
+/// Implemented for [`App`] so render code can call `cx.icon_label(icon)`.
+#[cfg(feature = "gpui")]
+pub trait ActiveIconLabels {
+    /// Returns the installed label for `icon`, or its metadata label when no
+    /// [`IconLabels`] have been installed.
+    fn icon_label(&self, icon: IconName) -> SharedString;
+}
+
+#[cfg(feature = "gpui")]
+impl ActiveIconLabels for App {
+    fn icon_label(&self, icon: IconName) -> SharedString {
+        self.try_global::<IconLabels>()
+            .map_or_else(|| icon.metadata().label.into(), |labels| labels.label(icon))
+    }
+}
+
+/// An icon in a requested style and size, resolved to the closest artwork
+/// the icon ships.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct IconVariant {
+    name: IconName,
+    requested: IconStyle,
+    size: Option<u16>,
+}
+
+impl From<IconName> for IconVariant {
+    fn from(name: IconName) -> Self {
+        name.variant(IconStyle::Outline)
+    }
+}
+
+impl IconVariant {
+    /// Prefers artwork drawn for `size` pixels: the smallest size-specific
+    /// file at least that large, otherwise the scalable artwork.
+    #[must_use]
+    pub const fn at_size(self, size: u16) -> Self {
+        Self {
+            size: Some(size),
+            ..self
+        }
+    }
+
+    /// Returns the icon this variant draws.
+    #[must_use]
+    pub const fn name(self) -> IconName {
+        self.name
+    }
+
+    /// Returns the style of the artwork actually used, which differs from
+    /// the requested one after a fallback.
+    #[must_use]
+    pub fn style(self) -> IconStyle {
+        self.artwork().style
+    }
+
+    /// Returns `true` when the requested style was missing and another one
+    /// is used instead.
+    #[must_use]
+    pub fn is_fallback(self) -> bool {
+        self.style() != self.requested
+    }
+
+    /// Returns `true` when the artwork keeps its own colors, so it should be
+    /// rendered as an image rather than tinted with the text color.
+    #[must_use]
+    pub fn is_multicolor(self) -> bool {
+        self.artwork().multicolor
+    }
+
+    /// Returns the asset path of the selected artwork.
+    #[must_use]
+    pub fn asset_path(self) -> &'static str {
+        self.artwork().asset_path
+    }
+
+    /// Returns the inline SVG markup of the selected artwork.
+    #[must_use]
+    pub fn svg(self) -> &'static str {
+        self.artwork().svg
+    }
+
+    fn artwork(self) -> &'static IconArtwork {
+        let artwork = self.name.artwork();
+        let style = self
+            .requested
+            .fallbacks()
+            .iter()
+            .copied()
+            .find(|style| artwork.iter().any(|artwork| artwork.style == *style))
+            .unwrap_or(IconStyle::Outline);
+        let candidates = || {
+            artwork
+                .iter()
+                .copied()
+                .filter(move |artwork| artwork.style == style)
+        };
+        let scalable = || candidates().find(|artwork| artwork.size.is_none());
+        let chosen = match self.size {
+            Some(size) => candidates()
+                .filter(|artwork| artwork.size.is_some_and(|drawn| drawn >= size))
+                .min_by_key(|artwork| artwork.size)
+                .or_else(scalable),
+            None => scalable(),
+        };
+        chosen
+            .or_else(|| candidates().max_by_key(|artwork| artwork.size))
+            .expect("the build script requires base artwork for every icon")
+    }
+}
+
 /// Asset source backed by the statically generated SVG table.
+#[cfg(feature = "gpui")]
 #[derive(Debug, Default, Clone, Copy)]
 pub struct IconAssetSource;
 
+#[cfg(feature = "gpui")]
 impl AssetSource for IconAssetSource {
     fn load(&self, path: &str) -> Result<Option<Cow<'static, [u8]>>> {
         Ok(ICON_SOURCE
@@ -39,9 +343,9 @@ impl AssetSource for IconAssetSource {
         }
 
         if path == "designsystem/icons" {
-            return Ok(IconName::ALL
+            return Ok(ICON_ARTWORK
                 .iter()
-                .map(|icon| SharedString::from(icon.asset_path()))
+                .map(|artwork| SharedString::from(artwork.asset_path))
                 .collect());
         }
 
@@ -56,6 +360,7 @@ pub struct IconLoader;
 impl IconLoader {
     /// Returns an [`AssetSource`] implementation that exposes the embedded
     /// icons.
+    #[cfg(feature = "gpui")]
     pub const fn asset_source() -> IconAssetSource {
         IconAssetSource
     }
@@ -79,6 +384,102 @@ impl IconLoader {
     pub fn all() -> &'static [(&'static str, IconName)] {
         ICON_NAMES
     }
+
+    /// Finds icons whose stem, label, tags, or synonyms match `query`, best
+    /// match first.
+    ///
+    /// Every whitespace-separated term must match some keyword, exactly, as
+    /// a prefix or substring, with one typo, or as an in-order
+    /// subsequence; names outrank tags and synonyms on ties. An empty query
+    /// returns every icon.
+    ///
+    /// ```
+    /// use designsystem::{IconLoader, IconName};
+    ///
+    /// assert_eq!(IconLoader::search("magnifier").first(), Some(&IconName::Search));
+    /// ```
+    #[must_use]
+    pub fn search(query: &str) -> Vec<IconName> {
+        let query = query.to_lowercase();
+        let terms: Vec<&str> = query.split_whitespace().collect();
+        let mut matches: Vec<(u32, &str, IconName)> = ICON_NAMES
+            .iter()
+            .filter_map(|(stem, icon)| {
+                let metadata = icon.metadata();
+                let name = stem.replace('-', " ");
+                let label = metadata.label.to_lowercase();
+                let keywords = metadata.tags.iter().chain(metadata.synonyms);
+                let mut score = 0;
+                for term in &terms {
+                    let named = [name.as_str(), label.as_str()]
+                        .into_iter()
+                        .filter_map(|keyword| keyword_score(term, keyword))
+                        .max()
+                        .map(|score| score + NAME_BONUS);
+                    let described = keywords
+                        .clone()
+                        .filter_map(|keyword| keyword_score(term, &keyword.to_lowercase()))
+                        .max();
+                    score += named.max(described)?;
+                }
+                Some((score, *stem, *icon))
+            })
+            .collect();
+        matches.sort_by(|a, b| b.0.cmp(&a.0).then(a.1.cmp(b.1)));
+        matches.into_iter().map(|(_, _, icon)| icon).collect()
+    }
+}
+
+/// Extra score for matching an icon's own name rather than a tag or synonym.
+const NAME_BONUS: u32 = 10;
+
+/// Scores how well `term` matches a lowercase `keyword` or one of its words.
+fn keyword_score(term: &str, keyword: &str) -> Option<u32> {
+    std::iter::once(keyword)
+        .chain(keyword.split_whitespace())
+        .filter_map(|candidate| {
+            if candidate == term {
+                Some(100)
+            } else if candidate.starts_with(term) {
+                Some(80)
+            } else if candidate.contains(term) {
+                Some(60)
+            } else if term.chars().count() >= 4 && within_one_edit(term, candidate) {
+                Some(50)
+            } else if is_subsequence(term, candidate) {
+                Some(20)
+            } else {
+                None
+            }
+        })
+        .max()
+}
+
+/// Returns `true` when one insertion, deletion, or substitution turns `a`
+/// into `b`.
+fn within_one_edit(a: &str, b: &str) -> bool {
+    let (a, b): (Vec<char>, Vec<char>) = (a.chars().collect(), b.chars().collect());
+    let (shorter, longer) = if a.len() <= b.len() { (a, b) } else { (b, a) };
+    if longer.len() - shorter.len() > 1 {
+        return false;
+    }
+    let prefix = shorter
+        .iter()
+        .zip(&longer)
+        .take_while(|(x, y)| x == y)
+        .count();
+    if prefix == shorter.len() {
+        return true;
+    }
+    let skip = usize::from(shorter.len() == longer.len());
+    shorter[prefix + skip..] == longer[prefix + 1..]
+}
+
+/// Returns `true` when the characters of `term` appear in order in
+/// `candidate`, as in `mgnf` for `magnifier`.
+fn is_subsequence(term: &str, candidate: &str) -> bool {
+    let mut chars = candidate.chars();
+    term.chars().all(|ch| chars.any(|other| other == ch))
 }
 
 #[cfg(test)]
@@ -93,4 +494,89 @@ mod tests {
             assert!(IconLoader::svg(*name).contains("<svg"));
         }
     }
+
+    #[test]
+    fn search_matches_synonyms_and_typos() {
+        assert_eq!(
+            IconLoader::search("magnifier").first(),
+            Some(&IconName::Search)
+        );
+        assert_eq!(IconLoader::search("serch").first(), Some(&IconName::Search));
+        assert_eq!(
+            IconLoader::search("team members").first(),
+            Some(&IconName::Users)
+        );
+        assert_eq!(IconLoader::search("").len(), IconName::ALL.len());
+        assert!(IconLoader::search("zzzz").is_empty());
+    }
+
+    #[test]
+    fn metadata_falls_back_to_the_stem() {
+        let palette = IconName::ProductPalette.metadata();
+        assert_eq!(palette.label, "Product palette");
+        assert_eq!(IconName::Search.metadata().category, "actions");
+    }
+
+    #[test]
+    #[cfg(feature = "gpui")]
+    fn labels_prefer_translations() {
+        let labels = IconLabels::localized(|key| match key {
+            "icon.users" => Some("Usuarios".to_owned()),
+            _ => None,
+        });
+        assert_eq!(labels.label(IconName::Users).as_ref(), "Usuarios");
+        assert_eq!(labels.label(IconName::Search).as_ref(), "Search");
+        assert_eq!(
+            IconLabels::default()
+                .label(IconName::ProductPalette)
+                .as_ref(),
+            "Product palette"
+        );
+        assert_eq!(IconName::ProductPalette.label_key(), "icon.product-palette");
+    }
+
+    #[test]
+    fn missing_styles_fall_back_to_outline() {
+        let icon = IconName::Users.variant(IconStyle::Filled);
+        assert_eq!(icon.style(), IconStyle::Outline);
+        assert!(icon.is_fallback());
+        assert_eq!(icon.asset_path(), IconName::Users.asset_path());
+
+        let duotone = IconName::Search.variant(IconStyle::Duotone);
+        assert_eq!(duotone.style(), IconStyle::Filled);
+    }
+
+    #[test]
+    fn sizes_prefer_dedicated_artwork() {
+        let outline = IconName::Search.variant(IconStyle::Outline);
+        assert_eq!(
+            outline.at_size(16).asset_path(),
+            "designsystem/icons/search@16.svg"
+        );
+        assert_eq!(
+            outline.at_size(12).asset_path(),
+            outline.at_size(16).asset_path()
+        );
+        assert_eq!(
+            outline.at_size(24).asset_path(),
+            IconName::Search.asset_path()
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "gpui")]
+    fn asset_source_serves_sized_artwork() {
+        assert!(IconAssetSource
+            .load("designsystem/icons/search@16.svg")
+            .unwrap()
+            .is_some());
+    }
+
+    #[test]
+    fn duotone_artwork_keeps_its_colors() {
+        let sparkles = IconName::Sparkles.variant(IconStyle::Duotone);
+        assert_eq!(sparkles.style(), IconStyle::Duotone);
+        assert!(sparkles.is_multicolor());
+        assert!(!IconVariant::from(IconName::Sparkles).is_multicolor());
+    }
 }
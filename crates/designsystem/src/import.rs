@@ -0,0 +1,642 @@
+//! Importer for W3C Design Tokens Community Group documents.
+
+use std::collections::BTreeMap;
+
+use serde_json::{Map, Value};
+use thiserror::Error;
+
+use crate::{
+    color::Color,
+    tokens::{
+        light_tokens, ColorPalette, DesignTokens, ElevationLevel, ElevationScale, SpacingScale,
+        TypographyScale, BASE_ELEVATION, BASE_SPACING,
+    },
+};
+
+/// Pixels per `rem` when converting relative dimensions.
+const REM_PX: f32 = 16.0;
+
+/// Color tokens every imported document must define; the component colors
+/// in [`ColorPalette::COMPONENT_TOKENS`] are derived when absent.
+const COLOR_TOKENS: [&str; 12] = [
+    "primary",
+    "on_primary",
+    "accent",
+    "on_accent",
+    "background",
+    "surface",
+    "surface_border",
+    "muted",
+    "on_muted",
+    "success",
+    "warning",
+    "danger",
+];
+
+/// Text sizes every imported document must define under `font.size`.
+const FONT_SIZE_TOKENS: [&str; 5] = ["display", "headline", "title", "body", "label"];
+
+/// Errors raised while importing a design-token document.
+#[derive(Debug, Error)]
+pub enum TokenImportError {
+    /// The document is not valid JSON.
+    #[error("failed to parse design tokens: {0}")]
+    Parse(#[from] serde_json::Error),
+    /// Required tokens are absent; every missing path is listed.
+    #[error("missing required tokens: {}", .0.join(", "))]
+    Missing(Vec<String>),
+    /// An alias points at a token that does not exist.
+    #[error("token '{path}' references unknown token '{target}'")]
+    UnknownAlias {
+        /// Token containing the alias.
+        path: String,
+        /// Path the alias points at.
+        target: String,
+    },
+    /// Aliases form a loop; the chain is listed in resolution order.
+    #[error("alias cycle: {}", .0.join(" -> "))]
+    AliasCycle(Vec<String>),
+    /// An alias points at a token whose declared `$type` differs from the
+    /// type expected where the alias is used.
+    #[error("token '{path}' expects a {expected} but '{target}' is a {found}")]
+    AliasType {
+        /// Token containing the alias.
+        path: String,
+        /// Path the alias points at.
+        target: String,
+        /// `$type` required where the alias is used.
+        expected: String,
+        /// `$type` declared by the target.
+        found: String,
+    },
+    /// A `typography` composite sits outside `typography.<size>`, where it
+    /// cannot be mapped onto the [`TypographyScale`].
+    #[error(
+        "typography token '{0}' is not supported; use typography.{{{}}}",
+        FONT_SIZE_TOKENS.join(",")
+    )]
+    UnsupportedTypography(String),
+    /// A token value cannot be converted into the expected type.
+    #[error("token '{path}' is invalid: {reason}")]
+    InvalidValue {
+        /// Dotted path of the offending token.
+        path: String,
+        /// Human readable description of the problem.
+        reason: String,
+    },
+}
+
+/// Builds [`DesignTokens`] from a W3C design-token JSON document.
+///
+/// The document uses the layout written by
+/// [`to_dtcg_json`](crate::to_dtcg_json): `color.*`, `font.family`,
+/// `font.size.*`, `spacing.*`, and `elevation.*`. Each text size may instead
+/// come from a `typography` composite at `typography.<size>`, whose
+/// `fontSize` is used; the family is taken from `typography.body` when
+/// `font.family` is absent. Aliases such as `"{color.primary}"` are
+/// resolved, including inside composite values, and must point at a token of
+/// the expected `$type`. Spacing and elevation tokens are optional and fall
+/// back to the built-in scales; component colors such as `color.sidebar` are
+/// derived from the core palette when absent.
+///
+/// # Errors
+///
+/// Returns [`TokenImportError::Missing`] listing every required token that is
+/// absent, [`TokenImportError::UnsupportedTypography`] for `typography`
+/// composites at any other path, [`TokenImportError::AliasType`] for aliases
+/// to a token of the wrong type, and other variants for unparsable JSON,
+/// broken aliases, or values of the wrong shape.
+pub fn from_dtcg_json(source: &str) -> Result<DesignTokens, TokenImportError> {
+    let document: Value = serde_json::from_str(source)?;
+    let table = TokenTable::new(&document);
+
+    table.check_required()?;
+    let has = |path: &str| table.tokens.contains_key(path);
+
+    let color = |name: &str| table.color(&format!("color.{name}"));
+    let core = ColorPalette {
+        primary: color("primary")?,
+        on_primary: color("on_primary")?,
+        accent: color("accent")?,
+        on_accent: color("on_accent")?,
+        background: color("background")?,
+        surface: color("surface")?,
+        surface_border: color("surface_border")?,
+        muted: color("muted")?,
+        on_muted: color("on_muted")?,
+        success: color("success")?,
+        warning: color("warning")?,
+        danger: color("danger")?,
+        ..light_tokens().colors
+    }
+    .with_derived_components();
+    let component = |name: &str, derived: Color| {
+        table
+            .optional(&format!("color.{name}"), TokenTable::color)
+            .map(|value| value.unwrap_or(derived))
+    };
+    let colors = ColorPalette {
+        selection: component("selection", core.selection)?,
+        ring: component("ring", core.ring)?,
+        input_border: component("input_border", core.input_border)?,
+        scrollbar_thumb: component("scrollbar_thumb", core.scrollbar_thumb)?,
+        tab_bar: component("tab_bar", core.tab_bar)?,
+        tab_active: component("tab_active", core.tab_active)?,
+        sidebar: component("sidebar", core.sidebar)?,
+        on_sidebar: component("on_sidebar", core.on_sidebar)?,
+        sidebar_border: component("sidebar_border", core.sidebar_border)?,
+        chart_1: component("chart_1", core.chart_1)?,
+        chart_2: component("chart_2", core.chart_2)?,
+        chart_3: component("chart_3", core.chart_3)?,
+        chart_4: component("chart_4", core.chart_4)?,
+        chart_5: component("chart_5", core.chart_5)?,
+        ..core
+    };
+
+    let size = |name: &str| {
+        let path = format!("font.size.{name}");
+        if has(&path) {
+            table.dimension(&path)
+        } else {
+            table.typography(&format!("typography.{name}"), "fontSize", dimension_value)
+        }
+    };
+    let font_family = if has("font.family") {
+        table.font_family("font.family")?
+    } else {
+        table.typography("typography.body", "fontFamily", font_family_value)?
+    };
+    let typography = TypographyScale {
+        font_family,
+        display: size("display")?,
+        headline: size("headline")?,
+        title: size("title")?,
+        body: size("body")?,
+        label: size("label")?,
+    };
+
+    let spacing_token = |name: &str, fallback: f32| {
+        table
+            .optional(&format!("spacing.{name}"), TokenTable::dimension)
+            .map(|value| value.unwrap_or(fallback))
+    };
+    let spacing = SpacingScale {
+        xxs: spacing_token("xxs", BASE_SPACING.xxs)?,
+        xs: spacing_token("xs", BASE_SPACING.xs)?,
+        sm: spacing_token("sm", BASE_SPACING.sm)?,
+        md: spacing_token("md", BASE_SPACING.md)?,
+        lg: spacing_token("lg", BASE_SPACING.lg)?,
+        xl: spacing_token("xl", BASE_SPACING.xl)?,
+        xxl: spacing_token("xxl", BASE_SPACING.xxl)?,
+    };
+
+    let elevation = |name: &str, fallback: ElevationLevel| {
+        table
+            .optional(&format!("elevation.{name}"), TokenTable::shadow)
+            .map(|level| level.unwrap_or(fallback))
+    };
+    let elevations = ElevationScale {
+        flat: elevation("flat", BASE_ELEVATION.flat)?,
+        raised: elevation("raised", BASE_ELEVATION.raised)?,
+        floating: elevation("floating", BASE_ELEVATION.floating)?,
+    };
+
+    Ok(DesignTokens {
+        colors,
+        typography,
+        spacing,
+        elevations,
+    })
+}
+
+/// Flattened view of a token document keyed by dotted path.
+struct TokenTable {
+    tokens: BTreeMap<String, RawToken>,
+}
+
+struct RawToken {
+    kind: Option<String>,
+    value: Value,
+}
+
+impl TokenTable {
+    fn new(document: &Value) -> Self {
+        let mut tokens = BTreeMap::new();
+        if let Some(root) = document.as_object() {
+            collect(root, "", None, &mut tokens);
+        }
+        Self { tokens }
+    }
+
+    /// Rejects misplaced `typography` composites, then lists every required
+    /// token the document lacks.
+    fn check_required(&self) -> Result<(), TokenImportError> {
+        if let Some(path) = self.tokens.iter().find_map(|(path, token)| {
+            let supported = path
+                .strip_prefix("typography.")
+                .is_some_and(|name| FONT_SIZE_TOKENS.contains(&name));
+            (token.kind.as_deref() == Some("typography") && !supported).then_some(path)
+        }) {
+            return Err(TokenImportError::UnsupportedTypography(path.clone()));
+        }
+
+        let has = |path: &str| self.tokens.contains_key(path);
+        let missing: Vec<String> = COLOR_TOKENS
+            .iter()
+            .map(|name| format!("color.{name}"))
+            .filter(|path| !has(path))
+            .chain(
+                (!has("font.family") && !has("typography.body")).then(|| "font.family".to_owned()),
+            )
+            .chain(
+                FONT_SIZE_TOKENS
+                    .iter()
+                    .filter(|name| !has(&format!("typography.{name}")))
+                    .map(|name| format!("font.size.{name}"))
+                    .filter(|path| !has(path)),
+            )
+            .collect();
+        if missing.is_empty() {
+            Ok(())
+        } else {
+            Err(TokenImportError::Missing(missing))
+        }
+    }
+
+    fn optional<T>(
+        &self,
+        path: &str,
+        parse: impl Fn(&Self, &str) -> Result<T, TokenImportError>,
+    ) -> Result<Option<T>, TokenImportError> {
+        if self.tokens.contains_key(path) {
+            parse(self, path).map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Returns the fully resolved value of the token at `path` after checking
+    /// its declared `$type`.
+    fn resolve(&self, path: &str, expected: &str) -> Result<Value, TokenImportError> {
+        let token = self
+            .tokens
+            .get(path)
+            .ok_or_else(|| TokenImportError::Missing(vec![path.to_owned()]))?;
+        if let Some(kind) = token.kind.as_deref().filter(|kind| *kind != expected) {
+            return Err(invalid(
+                path,
+                format!("expected $type '{expected}', found '{kind}'"),
+            ));
+        }
+        self.resolve_value(
+            &token.value,
+            path,
+            Some(expected),
+            &mut vec![path.to_owned()],
+        )
+    }
+
+    /// Resolves aliases inside `value`, checking each alias target against
+    /// `expected`. Members of composite values are checked against the type
+    /// their name implies.
+    fn resolve_value(
+        &self,
+        value: &Value,
+        path: &str,
+        expected: Option<&str>,
+        chain: &mut Vec<String>,
+    ) -> Result<Value, TokenImportError> {
+        match value {
+            Value::String(text) => {
+                let Some(target) = alias_target(text) else {
+                    return Ok(value.clone());
+                };
+                if chain.iter().any(|seen| seen == target) {
+                    let mut cycle = chain.clone();
+                    cycle.push(target.to_owned());
+                    return Err(TokenImportError::AliasCycle(cycle));
+                }
+                let token =
+                    self.tokens
+                        .get(target)
+                        .ok_or_else(|| TokenImportError::UnknownAlias {
+                            path: path.to_owned(),
+                            target: target.to_owned(),
+                        })?;
+                if let (Some(expected), Some(found)) = (expected, token.kind.as_deref()) {
+                    if expected != found {
+                        return Err(TokenImportError::AliasType {
+                            path: path.to_owned(),
+                            target: target.to_owned(),
+                            expected: expected.to_owned(),
+                            found: found.to_owned(),
+                        });
+                    }
+                }
+                chain.push(target.to_owned());
+                let resolved = self.resolve_value(&token.value, target, expected, chain);
+                chain.pop();
+                resolved
+            }
+            Value::Array(items) => items
+                .iter()
+                .map(|item| self.resolve_value(item, path, expected, chain))
+                .collect::<Result<_, _>>()
+                .map(Value::Array),
+            Value::Object(fields) => fields
+                .iter()
+                .map(|(key, field)| {
+                    let resolved = self.resolve_value(field, path, member_type(key), chain)?;
+                    Ok((key.clone(), resolved))
+                })
+                .collect::<Result<Map<_, _>, _>>()
+                .map(Value::Object),
+            _ => Ok(value.clone()),
+        }
+    }
+
+    fn color(&self, path: &str) -> Result<Color, TokenImportError> {
+        let value = self.resolve(path, "color")?;
+        color_value(&value).map_err(|reason| invalid(path, reason))
+    }
+
+    fn dimension(&self, path: &str) -> Result<f32, TokenImportError> {
+        let value = self.resolve(path, "dimension")?;
+        dimension_value(&value).map_err(|reason| invalid(path, reason))
+    }
+
+    fn font_family(&self, path: &str) -> Result<String, TokenImportError> {
+        let value = self.resolve(path, "fontFamily")?;
+        font_family_value(&value).map_err(|reason| invalid(path, reason))
+    }
+
+    /// Parses the `member` of the `typography` composite at `path`.
+    fn typography<T>(
+        &self,
+        path: &str,
+        member: &str,
+        parse: impl Fn(&Value) -> Result<T, String>,
+    ) -> Result<T, TokenImportError> {
+        let value = self.resolve(path, "typography")?;
+        let Some(field) = value.as_object().and_then(|fields| fields.get(member)) else {
+            return Err(invalid(
+                path,
+                format!("expected a typography object with '{member}'"),
+            ));
+        };
+        parse(field).map_err(|reason| invalid(&format!("{path}.{member}"), reason))
+    }
+
+    fn shadow(&self, path: &str) -> Result<ElevationLevel, TokenImportError> {
+        let value = self.resolve(path, "shadow")?;
+        // Layered shadows are collapsed to their first layer.
+        let layer = match &value {
+            Value::Array(layers) => layers.first().unwrap_or(&Value::Null),
+            other => other,
+        };
+        let Some(fields) = layer.as_object() else {
+            return Err(invalid(path, "expected a shadow object".into()));
+        };
+        let field = |name: &str| -> Result<f32, TokenImportError> {
+            fields.get(name).map_or(Ok(0.0), |value| {
+                dimension_value(value).map_err(|reason| invalid(&format!("{path}.{name}"), reason))
+            })
+        };
+        let opacity = match fields.get("color") {
+            Some(color) => {
+                let color = color_value(color)
+                    .map_err(|reason| invalid(&format!("{path}.color"), reason))?;
+                // One alpha step is ~0.004, so two decimals recover the
+                // opacity that was exported.
+                (color.opacity() * 100.0).round() / 100.0
+            }
+            None => 1.0,
+        };
+
+        Ok(ElevationLevel {
+            y_offset: field("offsetY")?,
+            blur_radius: field("blur")?,
+            spread_radius: field("spread")?,
+            opacity,
+        })
+    }
+}
+
+fn collect(
+    group: &Map<String, Value>,
+    prefix: &str,
+    inherited: Option<&str>,
+    tokens: &mut BTreeMap<String, RawToken>,
+) {
+    let group_kind = group.get("$type").and_then(Value::as_str).or(inherited);
+    for (key, child) in group {
+        if key.starts_with('$') {
+            continue;
+        }
+        let Some(child) = child.as_object() else {
+            continue;
+        };
+        let path = if prefix.is_empty() {
+            key.clone()
+        } else {
+            format!("{prefix}.{key}")
+        };
+        match child.get("$value") {
+            Some(value) => {
+                let kind = child.get("$type").and_then(Value::as_str).or(group_kind);
+                tokens.insert(
+                    path,
+                    RawToken {
+                        kind: kind.map(str::to_owned),
+                        value: value.clone(),
+                    },
+                );
+            }
+            None => collect(child, &path, group_kind, tokens),
+        }
+    }
+}
+
+fn alias_target(text: &str) -> Option<&str> {
+    text.strip_prefix('{')?.strip_suffix('}')
+}
+
+/// `$type` of the shadow and typography composite members this importer
+/// reads; other members are resolved without a type check.
+fn member_type(member: &str) -> Option<&'static str> {
+    match member {
+        "color" => Some("color"),
+        "offsetX" | "offsetY" | "blur" | "spread" | "fontSize" => Some("dimension"),
+        "fontFamily" => Some("fontFamily"),
+        _ => None,
+    }
+}
+
+fn invalid(path: &str, reason: String) -> TokenImportError {
+    TokenImportError::InvalidValue {
+        path: path.to_owned(),
+        reason,
+    }
+}
+
+/// Parses `#rrggbb`/`#rrggbbaa` strings or `{ "hex": .. }` objects.
+fn color_value(value: &Value) -> Result<Color, String> {
+    let hex = match value {
+        Value::String(hex) => hex.as_str(),
+        Value::Object(fields) => fields
+            .get("hex")
+            .and_then(Value::as_str)
+            .ok_or_else(|| "color objects must provide a 'hex' field".to_owned())?,
+        other => return Err(format!("expected a hex color, found {other}")),
+    };
+    Color::from_hex(hex).map_err(|err| err.to_string())
+}
+
+/// Parses a family name or a fallback list, keeping the first entry.
+fn font_family_value(value: &Value) -> Result<String, String> {
+    let family = match value {
+        Value::String(family) => Some(family.as_str()),
+        Value::Array(families) => families.first().and_then(Value::as_str),
+        _ => None,
+    };
+    match family.map(str::trim) {
+        Some(family) if !family.is_empty() => Ok(family.to_owned()),
+        _ => Err("expected a font family name".into()),
+    }
+}
+
+/// Parses `"12px"`, `"0.75rem"`, bare numbers, or `{ "value", "unit" }`
+/// objects into pixels.
+fn dimension_value(value: &Value) -> Result<f32, String> {
+    let (amount, unit) = match value {
+        Value::Number(number) => (number.as_f64(), "px"),
+        Value::String(text) => {
+            let text = text.trim();
+            let split = text
+                .find(|ch: char| ch.is_ascii_alphabetic())
+                .unwrap_or(text.len());
+            let (amount, unit) = text.split_at(split);
+            (
+                amount.trim().parse().ok(),
+                if unit.is_empty() { "px" } else { unit },
+            )
+        }
+        Value::Object(fields) => (
+            fields.get("value").and_then(Value::as_f64),
+            fields.get("unit").and_then(Value::as_str).unwrap_or("px"),
+        ),
+        _ => (None, ""),
+    };
+    let Some(amount) = amount.filter(|amount| amount.is_finite()) else {
+        return Err(format!("expected a dimension, found {value}"));
+    };
+    // Token dimensions are small pixel values, well within f32 precision.
+    #[allow(clippy::cast_possible_truncation)]
+    let amount = amount as f32;
+    match unit {
+        "px" => Ok(amount),
+        "rem" => Ok(amount * REM_PX),
+        other => Err(format!("unsupported unit '{other}'")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{export::to_dtcg_json, tokens::dark_tokens};
+
+    #[test]
+    fn exported_tokens_round_trip() {
+        let tokens = dark_tokens();
+        let imported = from_dtcg_json(&to_dtcg_json(&tokens)).unwrap();
+        assert_eq!(imported, tokens);
+    }
+
+    #[test]
+    fn aliases_resolve_through_chains() {
+        let mut document: Value = serde_json::from_str(&to_dtcg_json(&dark_tokens())).unwrap();
+        document["brand"] = serde_json::json!({
+            "$type": "color",
+            "violet": { "$value": "#7C3AED" },
+            "primary": { "$value": "{brand.violet}" }
+        });
+        document["color"]["primary"]["$value"] = "{brand.primary}".into();
+        document["spacing"]["md"]["$value"] = "0.75rem".into();
+
+        let tokens = from_dtcg_json(&document.to_string()).unwrap();
+        assert_eq!(tokens.colors.primary, Color::hex("#7c3aed"));
+        assert!((tokens.spacing.md - 12.0).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn missing_and_cyclic_tokens_report_paths() {
+        let mut document: Value = serde_json::from_str(&to_dtcg_json(&dark_tokens())).unwrap();
+        let colors = document["color"].as_object_mut().unwrap();
+        colors.remove("accent");
+        colors.remove("danger");
+        assert!(matches!(
+            from_dtcg_json(&document.to_string()),
+            Err(TokenImportError::Missing(paths))
+                if paths == ["color.accent", "color.danger"]
+        ));
+
+        document["color"]["accent"] = serde_json::json!({ "$value": "{color.danger}" });
+        document["color"]["danger"] = serde_json::json!({ "$value": "{color.accent}" });
+        assert!(matches!(
+            from_dtcg_json(&document.to_string()),
+            Err(TokenImportError::AliasCycle(chain)) if chain.len() == 3
+        ));
+    }
+
+    #[test]
+    fn typography_composites_supply_sizes() {
+        let tokens = dark_tokens();
+        let mut document: Value = serde_json::from_str(&to_dtcg_json(&tokens)).unwrap();
+        document.as_object_mut().unwrap().remove("font");
+        document["typography"] = serde_json::json!({
+            "$type": "typography",
+            "display": { "$value": { "fontFamily": "Inter", "fontSize": "2.25rem" } },
+            "headline": { "$value": { "fontSize": "28px" } },
+            "title": { "$value": { "fontSize": "{size.title}" } },
+            "body": { "$value": { "fontFamily": ["Inter", "sans-serif"], "fontSize": 14 } },
+            "label": { "$value": { "fontSize": "12px", "fontWeight": 500 } }
+        });
+        document["size"] =
+            serde_json::json!({ "title": { "$type": "dimension", "$value": "20px" } });
+
+        let imported = from_dtcg_json(&document.to_string()).unwrap();
+        assert_eq!(imported.typography.font_family, "Inter");
+        assert!((imported.typography.display - 36.0).abs() < f32::EPSILON);
+        assert!((imported.typography.title - 20.0).abs() < f32::EPSILON);
+        assert!((imported.typography.body - 14.0).abs() < f32::EPSILON);
+
+        document["text"] = serde_json::json!({
+            "caption": { "$type": "typography", "$value": { "fontSize": "11px" } }
+        });
+        assert!(matches!(
+            from_dtcg_json(&document.to_string()),
+            Err(TokenImportError::UnsupportedTypography(path)) if path == "text.caption"
+        ));
+    }
+
+    #[test]
+    fn aliases_must_match_the_expected_type() {
+        let mut document: Value = serde_json::from_str(&to_dtcg_json(&dark_tokens())).unwrap();
+        document["spacing"]["md"]["$value"] = "{color.primary}".into();
+        assert!(matches!(
+            from_dtcg_json(&document.to_string()),
+            Err(TokenImportError::AliasType { path, target, expected, found })
+                if path == "spacing.md"
+                    && target == "color.primary"
+                    && expected == "dimension"
+                    && found == "color"
+        ));
+
+        let mut document: Value = serde_json::from_str(&to_dtcg_json(&dark_tokens())).unwrap();
+        document["elevation"]["raised"]["$value"]["blur"] = "{color.primary}".into();
+        assert!(matches!(
+            from_dtcg_json(&document.to_string()),
+            Err(TokenImportError::AliasType { path, .. }) if path == "elevation.raised"
+        ));
+    }
+}
@@ -1,16 +1,56 @@
 #![doc = include_str!("../README.md")]
 #![warn(clippy::pedantic, missing_docs, unreachable_pub)]
 
+mod appearance;
+mod color;
+mod contrast;
+mod export;
+#[cfg(feature = "gpui")]
+mod icon_pack;
 mod icons;
+mod import;
+#[cfg(feature = "gpui")]
+mod styled;
+mod svg;
 mod theme;
+mod theme_file;
+#[cfg(feature = "gpui")]
+mod theme_watch;
 mod tokens;
+mod transition;
 
-pub use icons::{IconAssetSource, IconLoader, IconName};
-pub use theme::{ThemeDefinition, ThemeError, ThemeRegistry, ThemeVariant};
+pub use appearance::{AppearanceSource, ThemePreference};
+pub use color::{Color, ColorError};
+pub use contrast::{
+    audit_contrast, contrast_ratio, correct_contrast, relative_luminance, srgb_to_linear,
+    ColorAdjustment, ContrastCheck, ContrastCorrection, ContrastError, ContrastReport, WcagLevel,
+};
+pub use export::{
+    export_tokens, to_css_variables, to_dtcg_json, to_figma_tokens, to_tailwind_config, TokenFormat,
+};
+#[cfg(feature = "gpui")]
+pub use icon_pack::{IconPackError, IconPackSource};
+#[cfg(feature = "gpui")]
+pub use icons::{ActiveIconLabels, IconAssetSource, IconLabels};
+pub use icons::{IconLoader, IconMetadata, IconName, IconStyle, IconVariant};
+pub use import::{from_dtcg_json, TokenImportError};
+#[cfg(feature = "gpui")]
+pub use styled::TokenStyled;
+pub use svg::{optimize_svg, OptimizedSvg, SvgError, SvgOptions};
+#[cfg(feature = "gpui")]
+pub use theme::ActiveTokens;
+pub use theme::{
+    ThemeAppearance, ThemeChange, ThemeDefinition, ThemeError, ThemeId, ThemeRegistry, ThemeVariant,
+};
+pub use theme_file::ThemeFormat;
+#[cfg(feature = "gpui")]
+pub use theme_watch::{ThemeWatcher, THEME_DIR_ENV};
 pub use tokens::{
     dark_tokens, high_contrast_tokens, light_tokens, ColorPalette, DesignTokens, ElevationLevel,
-    ElevationScale, SpacingScale, SpacingToken, TypographyScale,
+    ElevationScale, ElevationToken, PaletteError, PaletteMode, PaletteSeed, SpacingScale,
+    SpacingToken, TextRole, TypographyScale,
 };
+pub use transition::ThemeTransition;
 
 /// Installs the design system defaults into the supplied application context.
 ///
@@ -24,6 +64,7 @@ pub use tokens::{
 ///     ThemeRegistry::new().install(cx);
 /// });
 /// ```
+#[cfg(feature = "gpui")]
 #[allow(clippy::module_name_repetitions)]
 pub fn install_defaults(app: gpui::Application) -> gpui::Application {
     app.with_assets(IconLoader::asset_source())
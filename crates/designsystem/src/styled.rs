@@ -0,0 +1,127 @@
+//! Styling extensions that resolve design tokens against the active theme.
+
+use gpui::{hsla, point, px, App, BoxShadow, FontWeight, Styled};
+
+use crate::{
+    theme::ActiveTokens,
+    tokens::{ElevationLevel, ElevationToken, SpacingToken, TextRole},
+};
+
+impl ElevationLevel {
+    /// Converts the level into the shadow list accepted by
+    /// [`Styled::shadow`].
+    ///
+    /// Levels with no opacity produce an empty list, which clears any shadow.
+    #[must_use]
+    pub fn box_shadows(&self) -> Vec<BoxShadow> {
+        if self.opacity <= 0.0 {
+            return Vec::new();
+        }
+        vec![BoxShadow {
+            color: hsla(0.0, 0.0, 0.0, self.opacity.clamp(0.0, 1.0)),
+            offset: point(px(0.0), px(self.y_offset)),
+            blur_radius: px(self.blur_radius),
+            spread_radius: px(self.spread_radius),
+        }]
+    }
+}
+
+impl TextRole {
+    /// Font weight paired with the role's size.
+    #[must_use]
+    pub const fn weight(self) -> FontWeight {
+        match self {
+            TextRole::Display | TextRole::Headline => FontWeight::BOLD,
+            TextRole::Title => FontWeight::SEMIBOLD,
+            TextRole::Body => FontWeight::NORMAL,
+            TextRole::Label => FontWeight::MEDIUM,
+        }
+    }
+}
+
+/// Token-aware styling helpers available on every GPUI [`Styled`] element.
+///
+/// Values are read from the active theme on every call, so switching to the
+/// high-contrast theme widens spacing and enlarges text on the next render.
+///
+/// ```no_run
+/// use designsystem::{ElevationToken, SpacingToken, TextRole, TokenStyled as _};
+/// use gpui::{div, App, Div};
+///
+/// fn overlay(cx: &App) -> Div {
+///     div()
+///         .p_token(SpacingToken::LG, cx)
+///         .gap_token(SpacingToken::SM, cx)
+///         .text_style(TextRole::Body, cx)
+///         .elevation(ElevationToken::Floating, cx)
+/// }
+/// ```
+pub trait TokenStyled: Styled + Sized {
+    /// Sets the gap between flex or grid children to a spacing token.
+    #[must_use]
+    fn gap_token(self, spacing: SpacingToken, cx: &App) -> Self {
+        self.gap(px(cx.design_tokens().spacing.as_px(spacing)))
+    }
+
+    /// Sets the padding on every side to a spacing token.
+    #[must_use]
+    fn p_token(self, spacing: SpacingToken, cx: &App) -> Self {
+        self.p(px(cx.design_tokens().spacing.as_px(spacing)))
+    }
+
+    /// Applies the font family, size, and weight for a text role.
+    #[must_use]
+    fn text_style(self, role: TextRole, cx: &App) -> Self {
+        let typography = &cx.design_tokens().typography;
+        self.font_family(typography.font_family.clone())
+            .text_size(px(typography.size(role)))
+            .font_weight(role.weight())
+    }
+
+    /// Applies the active theme's shadow for `elevation`.
+    #[must_use]
+    fn elevation(self, elevation: ElevationToken, cx: &App) -> Self {
+        self.shadow(cx.design_tokens().elevations.level(elevation).box_shadows())
+    }
+
+    /// Applies the active theme's raised shadow, as used by cards.
+    #[must_use]
+    fn raised(self, cx: &App) -> Self {
+        self.elevation(ElevationToken::Raised, cx)
+    }
+
+    /// Applies the active theme's floating shadow, as used by overlays.
+    #[must_use]
+    fn floating(self, cx: &App) -> Self {
+        self.elevation(ElevationToken::Floating, cx)
+    }
+}
+
+impl<T: Styled> TokenStyled for T {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tokens::{high_contrast_tokens, light_tokens};
+
+    #[test]
+    fn elevation_levels_become_box_shadows() {
+        let elevations = light_tokens().elevations;
+        assert!(elevations
+            .level(ElevationToken::Flat)
+            .box_shadows()
+            .is_empty());
+
+        let raised = elevations.level(ElevationToken::Raised).box_shadows();
+        assert_eq!(raised.len(), 1);
+        assert_eq!(raised[0].offset, point(px(0.0), px(8.0)));
+        assert_eq!(raised[0].blur_radius, px(16.0));
+        assert!((raised[0].color.a - 0.12).abs() < f32::EPSILON);
+
+        let floating = high_contrast_tokens()
+            .elevations
+            .level(ElevationToken::Floating)
+            .box_shadows();
+        assert!(floating[0].color.a > raised[0].color.a);
+    }
+}
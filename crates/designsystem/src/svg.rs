@@ -0,0 +1,487 @@
+//! SVG validation and minification shared by the icon build script and the
+//! `cargo xtask icons` importer.
+
+use std::fmt::Write as _;
+
+use roxmltree::{Document, Node};
+use thiserror::Error;
+
+const SVG_NAMESPACE: &str = "http://www.w3.org/2000/svg";
+const XLINK_NAMESPACE: &str = "http://www.w3.org/1999/xlink";
+
+/// SVG elements that carry no drawing and are removed.
+const METADATA_ELEMENTS: [&str; 3] = ["metadata", "title", "desc"];
+
+/// Attributes that only editors read and are removed.
+const EDITOR_ATTRIBUTES: [&str; 2] = ["version", "enable-background"];
+
+/// Presentation properties checked for fixed colors.
+const PAINT_PROPERTIES: [&str; 4] = ["fill", "stroke", "stop-color", "color"];
+
+/// Errors raised while validating an SVG document.
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum SvgError {
+    /// The document is not well-formed XML.
+    #[error("malformed SVG: {0}")]
+    Parse(String),
+    /// The root element is not an SVG `<svg>` element.
+    #[error("root element is <{0}>, expected <svg>")]
+    NotSvg(String),
+    /// The root has no `viewBox` and no numeric `width`/`height` to derive
+    /// one from.
+    #[error("missing viewBox; add one or numeric width and height")]
+    MissingViewBox,
+    /// A monochrome icon paints with a fixed color instead of
+    /// `currentColor`.
+    #[error("<{element}> sets {property}=\"{value}\"; monochrome icons must use currentColor")]
+    FixedColor {
+        /// Element carrying the color.
+        element: String,
+        /// Paint property, such as `fill`.
+        property: String,
+        /// The offending value.
+        value: String,
+    },
+    /// A monochrome icon paints with a gradient or pattern, which cannot
+    /// follow the text color.
+    #[error("<{element}> sets {property}=\"{value}\"; monochrome icons cannot use paint servers")]
+    PaintServer {
+        /// Element carrying the paint.
+        element: String,
+        /// Paint property, such as `fill`.
+        property: String,
+        /// The `url(#…)` reference.
+        value: String,
+    },
+    /// A monochrome icon never paints with `currentColor`, so it would
+    /// render in the default black whatever the text color.
+    #[error("monochrome icons must paint with currentColor; add fill or stroke=\"currentColor\"")]
+    MissingCurrentColor,
+}
+
+/// Validation rules applied by [`optimize_svg`].
+#[derive(Debug, Clone, Copy)]
+pub struct SvgOptions {
+    /// Whether fixed colors and paint servers are allowed, as in duotone
+    /// artwork. When `false` every paint must be `currentColor`, `none`, or
+    /// `transparent`, and at least one must be `currentColor`.
+    pub allow_fixed_colors: bool,
+    /// Attributes added to the root `<svg>` when it does not set them.
+    pub default_attributes: &'static [(&'static str, &'static str)],
+}
+
+impl SvgOptions {
+    /// Rules for artwork tinted with the text color.
+    pub const MONOCHROME: Self = Self {
+        allow_fixed_colors: false,
+        default_attributes: &[],
+    };
+
+    /// Rules for artwork that keeps its own colors.
+    pub const MULTICOLOR: Self = Self {
+        allow_fixed_colors: true,
+        default_attributes: &[],
+    };
+}
+
+/// A validated and minified SVG document.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OptimizedSvg {
+    /// Minified markup.
+    pub svg: String,
+    /// Size of the source document in bytes.
+    pub original_bytes: usize,
+    /// Whether the artwork paints with fixed colors.
+    pub multicolor: bool,
+}
+
+impl OptimizedSvg {
+    /// Returns how many bytes minification removed.
+    #[must_use]
+    pub fn saved_bytes(&self) -> usize {
+        self.original_bytes.saturating_sub(self.svg.len())
+    }
+}
+
+/// Parses `source`, checks it against `options`, and re-serializes it
+/// without comments, metadata, editor namespaces, or insignificant
+/// whitespace.
+///
+/// A missing `viewBox` is derived from numeric `width` and `height`.
+///
+/// # Errors
+///
+/// Returns [`SvgError::Parse`] for malformed XML, [`SvgError::NotSvg`] when
+/// the root is not `<svg>`, [`SvgError::MissingViewBox`] when no viewBox can
+/// be determined, and [`SvgError::FixedColor`],
+/// [`SvgError::PaintServer`], or [`SvgError::MissingCurrentColor`] when
+/// monochrome artwork does not paint with `currentColor`.
+pub fn optimize_svg(source: &str, options: SvgOptions) -> Result<OptimizedSvg, SvgError> {
+    let document = Document::parse(source).map_err(|err| SvgError::Parse(err.to_string()))?;
+    let root = document.root_element();
+    if root.tag_name().name() != "svg" || root.tag_name().namespace() != Some(SVG_NAMESPACE) {
+        return Err(SvgError::NotSvg(root.tag_name().name().to_owned()));
+    }
+
+    let mut root_attributes = Vec::new();
+    if root.attribute("viewBox").is_none() {
+        let dimension = |name: &str| {
+            root.attribute(name)
+                .map(|value| value.trim_end_matches("px"))
+                .and_then(|value| value.parse::<f32>().ok())
+        };
+        match (dimension("width"), dimension("height")) {
+            (Some(width), Some(height)) => {
+                root_attributes.push(("viewBox", format!("0 0 {width} {height}")));
+            }
+            _ => return Err(SvgError::MissingViewBox),
+        }
+    }
+    for (name, value) in options.default_attributes {
+        if root.attribute(*name).is_none() {
+            root_attributes.push((*name, (*value).to_owned()));
+        }
+    }
+
+    let mut writer = SvgWriter {
+        svg: String::with_capacity(source.len()),
+        options,
+        multicolor: false,
+        current_color: false,
+    };
+    writer.element(root, &root_attributes)?;
+    if !options.allow_fixed_colors && !writer.current_color {
+        return Err(SvgError::MissingCurrentColor);
+    }
+    Ok(OptimizedSvg {
+        svg: writer.svg,
+        original_bytes: source.len(),
+        multicolor: writer.multicolor,
+    })
+}
+
+struct SvgWriter {
+    svg: String,
+    options: SvgOptions,
+    multicolor: bool,
+    /// Whether any paint uses `currentColor`.
+    current_color: bool,
+}
+
+impl SvgWriter {
+    fn element(&mut self, node: Node<'_, '_>, extra: &[(&str, String)]) -> Result<(), SvgError> {
+        let name = node.tag_name().name();
+        self.svg.push('<');
+        self.svg.push_str(name);
+        if node.parent_element().is_none() {
+            let _ = write!(self.svg, " xmlns=\"{SVG_NAMESPACE}\"");
+            let uses_xlink = node.descendants().any(|node| {
+                node.attributes()
+                    .any(|attribute| attribute.namespace() == Some(XLINK_NAMESPACE))
+            });
+            if uses_xlink {
+                let _ = write!(self.svg, " xmlns:xlink=\"{XLINK_NAMESPACE}\"");
+            }
+        }
+
+        for attribute in node.attributes() {
+            let prefix = match attribute.namespace() {
+                None => "",
+                Some(XLINK_NAMESPACE) => "xlink:",
+                Some(_) => continue,
+            };
+            let attribute_name = attribute.name();
+            if prefix.is_empty()
+                && (EDITOR_ATTRIBUTES.contains(&attribute_name)
+                    || attribute_name.starts_with("data-"))
+            {
+                continue;
+            }
+            self.check_paint(name, attribute_name, attribute.value())?;
+            let _ = write!(
+                self.svg,
+                " {prefix}{attribute_name}=\"{}\"",
+                escape(attribute.value(), true)
+            );
+        }
+        if name == "style" {
+            for css in node.children().filter_map(|child| child.text()) {
+                self.check_stylesheet(css)?;
+            }
+        }
+        for (attribute, value) in extra {
+            self.check_paint(name, attribute, value)?;
+            let _ = write!(self.svg, " {attribute}=\"{}\"", escape(value, true));
+        }
+
+        let children: Vec<_> = node
+            .children()
+            .filter(|child| {
+                if child.is_element() {
+                    child.tag_name().namespace() == Some(SVG_NAMESPACE)
+                        && !METADATA_ELEMENTS.contains(&child.tag_name().name())
+                } else {
+                    // Comments and processing instructions are dropped too.
+                    child.is_text() && child.text().is_some_and(|text| !text.trim().is_empty())
+                }
+            })
+            .collect();
+        if children.is_empty() {
+            self.svg.push_str("/>");
+            return Ok(());
+        }
+
+        self.svg.push('>');
+        for child in children {
+            if child.is_element() {
+                self.element(child, &[])?;
+            } else if let Some(text) = child.text() {
+                self.svg.push_str(&escape(text.trim(), false));
+            }
+        }
+        let _ = write!(self.svg, "</{name}>");
+        Ok(())
+    }
+
+    /// Checks a paint attribute, or every paint declared in a `style`
+    /// attribute, against the color rules.
+    fn check_paint(&mut self, element: &str, attribute: &str, value: &str) -> Result<(), SvgError> {
+        if attribute == "style" {
+            self.check_declarations(element, value)
+        } else {
+            self.check_value(element, attribute, value)
+        }
+    }
+
+    /// Checks the declarations of every rule in a `<style>` element, so
+    /// class-based fills follow the same color rules as attributes.
+    fn check_stylesheet(&mut self, css: &str) -> Result<(), SvgError> {
+        let mut uncommented = String::with_capacity(css.len());
+        let mut rest = css;
+        while let Some((before, comment)) = rest.split_once("/*") {
+            uncommented.push_str(before);
+            rest = comment.split_once("*/").map_or("", |(_, after)| after);
+        }
+        uncommented.push_str(rest);
+
+        for rule in uncommented.split('}') {
+            // The innermost block holds the declarations, also inside
+            // at-rules such as `@media`.
+            if let Some((_, body)) = rule.rsplit_once('{') {
+                self.check_declarations("style", body)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn check_declarations(&mut self, element: &str, css: &str) -> Result<(), SvgError> {
+        for (property, value) in css
+            .split(';')
+            .filter_map(|declaration| declaration.split_once(':'))
+        {
+            self.check_value(element, property.trim(), value)?;
+        }
+        Ok(())
+    }
+
+    fn check_value(&mut self, element: &str, property: &str, value: &str) -> Result<(), SvgError> {
+        let value = value.trim();
+        let paint = value
+            .len()
+            .checked_sub("!important".len())
+            .filter(|&end| value[end..].eq_ignore_ascii_case("!important"))
+            .map_or(value, |end| value[..end].trim_end());
+        if !PAINT_PROPERTIES
+            .iter()
+            .any(|paint_property| paint_property.eq_ignore_ascii_case(property))
+        {
+            return Ok(());
+        }
+        if paint.eq_ignore_ascii_case("currentColor") {
+            self.current_color = true;
+            return Ok(());
+        }
+        if ["none", "transparent", "inherit"]
+            .iter()
+            .any(|keyword| keyword.eq_ignore_ascii_case(paint))
+        {
+            return Ok(());
+        }
+        let paint_server = paint
+            .get(..4)
+            .is_some_and(|prefix| prefix.eq_ignore_ascii_case("url("));
+        if paint_server && !self.options.allow_fixed_colors {
+            return Err(SvgError::PaintServer {
+                element: element.to_owned(),
+                property: property.to_owned(),
+                value: value.to_owned(),
+            });
+        }
+        if !self.options.allow_fixed_colors {
+            return Err(SvgError::FixedColor {
+                element: element.to_owned(),
+                property: property.to_owned(),
+                value: value.to_owned(),
+            });
+        }
+        self.multicolor = true;
+        Ok(())
+    }
+}
+
+fn escape(text: &str, attribute: bool) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for ch in text.chars() {
+        match ch {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' if attribute => escaped.push_str("&quot;"),
+            _ => escaped.push(ch),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EDITOR_EXPORT: &str = r##"<?xml version="1.0" encoding="UTF-8"?>
+<!-- Generator: Sketch -->
+<svg xmlns="http://www.w3.org/2000/svg" xmlns:sodipodi="http://sodipodi.sourceforge.net/DTD/sodipodi-0.dtd" version="1.1" width="24" height="24" stroke="currentColor" sodipodi:docname="search.svg">
+  <title>Search</title>
+  <metadata>exported</metadata>
+  <sodipodi:namedview pagecolor="#ffffff" />
+  <circle cx="11" cy="11" r="7" data-layer="1" />
+</svg>
+"##;
+
+    #[test]
+    fn strips_metadata_and_derives_view_box() {
+        let optimized = optimize_svg(EDITOR_EXPORT, SvgOptions::MONOCHROME).unwrap();
+        assert_eq!(
+            optimized.svg,
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"24\" height=\"24\" \
+             stroke=\"currentColor\" viewBox=\"0 0 24 24\"><circle cx=\"11\" cy=\"11\" r=\"7\"/></svg>"
+        );
+        assert!(optimized.saved_bytes() > 0);
+        assert!(!optimized.multicolor);
+    }
+
+    #[test]
+    fn rejects_malformed_and_unsized_documents() {
+        assert!(matches!(
+            optimize_svg(
+                "<svg xmlns=\"http://www.w3.org/2000/svg\"><path></svg>",
+                SvgOptions::MONOCHROME
+            ),
+            Err(SvgError::Parse(_))
+        ));
+        assert_eq!(
+            optimize_svg(
+                "<svg xmlns=\"http://www.w3.org/2000/svg\"/>",
+                SvgOptions::MONOCHROME
+            ),
+            Err(SvgError::MissingViewBox)
+        );
+        assert_eq!(
+            optimize_svg("<html/>", SvgOptions::MONOCHROME),
+            Err(SvgError::NotSvg("html".into()))
+        );
+    }
+
+    #[test]
+    fn fixed_colors_require_multicolor_rules() {
+        let source = "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"0 0 24 24\">\
+                      <path style=\"fill: #f59e0b\" d=\"M0 0h24\"/></svg>";
+        assert_eq!(
+            optimize_svg(source, SvgOptions::MONOCHROME),
+            Err(SvgError::FixedColor {
+                element: "path".into(),
+                property: "fill".into(),
+                value: "#f59e0b".into(),
+            })
+        );
+        assert!(
+            optimize_svg(source, SvgOptions::MULTICOLOR)
+                .unwrap()
+                .multicolor
+        );
+    }
+
+    #[test]
+    fn monochrome_icons_must_use_current_color() {
+        let unpainted = "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"0 0 24 24\">\
+                         <path d=\"M0 0h24\"/></svg>";
+        assert_eq!(
+            optimize_svg(unpainted, SvgOptions::MONOCHROME),
+            Err(SvgError::MissingCurrentColor)
+        );
+        assert!(optimize_svg(unpainted, SvgOptions::MULTICOLOR).is_ok());
+
+        let defaults = SvgOptions {
+            default_attributes: &[("stroke", "currentColor")],
+            ..SvgOptions::MONOCHROME
+        };
+        assert!(optimize_svg(unpainted, defaults).is_ok());
+    }
+
+    #[test]
+    fn paint_servers_are_multicolor_only() {
+        let source = "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"0 0 24 24\">\
+                      <defs><linearGradient id=\"g\"><stop offset=\"0\" stop-color=\"#f59e0b\"/>\
+                      </linearGradient></defs><path fill=\"url(#g)\" d=\"M0 0h24\"/></svg>";
+        assert!(
+            optimize_svg(source, SvgOptions::MULTICOLOR)
+                .unwrap()
+                .multicolor
+        );
+
+        let gradient_only = source.replace("#f59e0b", "currentColor");
+        assert_eq!(
+            optimize_svg(&gradient_only, SvgOptions::MONOCHROME),
+            Err(SvgError::PaintServer {
+                element: "path".into(),
+                property: "fill".into(),
+                value: "url(#g)".into(),
+            })
+        );
+    }
+
+    #[test]
+    fn paint_keywords_ignore_case() {
+        let source = "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"0 0 24 24\">\
+                      <path fill=\"currentcolor\" style=\"stroke: CURRENTCOLOR !important\" \
+                      d=\"M0 0h24\"/></svg>";
+        assert!(
+            !optimize_svg(source, SvgOptions::MONOCHROME)
+                .unwrap()
+                .multicolor
+        );
+    }
+
+    #[test]
+    fn stylesheets_follow_the_color_rules() {
+        let source = "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"0 0 24 24\">\
+                      <style>/* brand */ @media screen { .a { stroke: currentColor; } }\
+                      .b { FILL: #F59E0B }</style>\
+                      <path class=\"a b\" d=\"M0 0h24\"/></svg>";
+        assert_eq!(
+            optimize_svg(source, SvgOptions::MONOCHROME),
+            Err(SvgError::FixedColor {
+                element: "style".into(),
+                property: "FILL".into(),
+                value: "#F59E0B".into(),
+            })
+        );
+        assert!(
+            optimize_svg(source, SvgOptions::MULTICOLOR)
+                .unwrap()
+                .multicolor
+        );
+
+        let monochrome = source.replace("#F59E0B", "currentColor");
+        assert!(optimize_svg(&monochrome, SvgOptions::MONOCHROME).is_ok());
+    }
+}
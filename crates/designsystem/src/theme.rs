@@ -1,21 +1,92 @@
 //! Theme registry that bridges Workspace tokens with `gpui-component`.
 
 use std::{
-    collections::HashMap,
+    collections::{HashMap, HashSet},
+    fmt, fs, io,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex, RwLock},
+};
+#[cfg(feature = "gpui")]
+use std::{
     rc::Rc,
-    sync::{Arc, Mutex},
+    sync::atomic::{AtomicU64, Ordering},
 };
 
-use gpui::{px, App};
+#[cfg(feature = "gpui")]
+use gpui::{px, App, Global, Subscription, Window, WindowId};
+#[cfg(feature = "gpui")]
 use gpui_component::theme::{Theme, ThemeConfig, ThemeConfigColors, ThemeMode};
 use once_cell::sync::Lazy;
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use thiserror::Error;
 
-use crate::tokens::{dark_tokens, high_contrast_tokens, light_tokens, DesignTokens};
+use crate::{
+    appearance::{AppearanceSource, ThemePreference},
+    theme_file::{ThemeFile, ThemeFormat},
+    tokens::{dark_tokens, high_contrast_tokens, light_tokens, DesignTokens},
+    transition::ThemeTransition,
+};
+
+/// Returns a `'static` copy of `value`, reusing previously interned strings.
+///
+/// Runtime themes are few and long-lived, so leaking one copy of each distinct
+/// identifier keeps [`ThemeVariant`] `Copy` without unbounded growth.
+fn intern(value: &str) -> &'static str {
+    static INTERNED: Lazy<Mutex<HashSet<&'static str>>> = Lazy::new(Mutex::default);
+
+    let mut interned = INTERNED.lock().expect("intern mutex poisoned");
+    if let Some(existing) = interned.get(value) {
+        return existing;
+    }
+    let leaked: &'static str = Box::leak(value.to_owned().into_boxed_str());
+    interned.insert(leaked);
+    leaked
+}
+
+/// Identifier for a theme registered at runtime, such as one loaded from disk.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ThemeId(&'static str);
+
+impl ThemeId {
+    /// Creates an identifier from a slug such as `"brand-dark"`.
+    #[must_use]
+    pub fn new(id: &str) -> Self {
+        Self(intern(id))
+    }
+
+    /// Returns the slug for the identifier.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        self.0
+    }
+}
+
+impl fmt::Debug for ThemeId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.0, f)
+    }
+}
+
+impl Serialize for ThemeId {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.0)
+    }
+}
+
+impl<'de> Deserialize<'de> for ThemeId {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let id = String::deserialize(deserializer)?;
+        Ok(Self::new(&id))
+    }
+}
 
 /// Available theme variants shipped with the design system.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+///
+/// Variants serialize as their [`slug`](ThemeVariant::as_str), such as
+/// `"high-contrast"` or `"brand"`, the same strings persisted settings use.
+/// The variant names written by earlier releases, such as `"HighContrast"`,
+/// still deserialize to the built-ins.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum ThemeVariant {
     /// Default light theme optimized for bright environments.
     Light,
@@ -23,9 +94,18 @@ pub enum ThemeVariant {
     Dark,
     /// High contrast theme targeting accessibility needs.
     HighContrast,
+    /// Theme registered at runtime through [`ThemeRegistry::register`] or one
+    /// of the theme file loaders.
+    Custom(ThemeId),
 }
 
 impl ThemeVariant {
+    const BUILT_INS: [ThemeVariant; 3] = [
+        ThemeVariant::Light,
+        ThemeVariant::Dark,
+        ThemeVariant::HighContrast,
+    ];
+
     #[must_use]
     /// Returns the string slug used for the variant.
     pub const fn as_str(self) -> &'static str {
@@ -33,13 +113,60 @@ impl ThemeVariant {
             ThemeVariant::Light => "light",
             ThemeVariant::Dark => "dark",
             ThemeVariant::HighContrast => "high-contrast",
+            ThemeVariant::Custom(id) => id.as_str(),
+        }
+    }
+
+    /// Returns `true` for the variants compiled into the design system.
+    #[must_use]
+    pub const fn is_built_in(self) -> bool {
+        !matches!(self, ThemeVariant::Custom(_))
+    }
+
+    /// Maps a built-in slug, or the variant name persisted before custom
+    /// themes existed (`"HighContrast"`), to its built-in variant.
+    fn built_in_from_str(name: &str) -> Option<Self> {
+        match name {
+            "light" | "Light" => Some(Self::Light),
+            "dark" | "Dark" => Some(Self::Dark),
+            "high-contrast" | "HighContrast" => Some(Self::HighContrast),
+            _ => None,
         }
     }
+}
+
+impl Serialize for ThemeVariant {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for ThemeVariant {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        // Custom themes cannot use a built-in slug, so any other slug names
+        // a custom theme. It may not be registered yet; registries fall back
+        // to the light theme for it until it is.
+        let slug = String::deserialize(deserializer)?;
+        Ok(Self::built_in_from_str(&slug).unwrap_or_else(|| Self::Custom(ThemeId::new(&slug))))
+    }
+}
 
+/// Whether a theme renders light surfaces or dark surfaces.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ThemeAppearance {
+    /// Dark text on light surfaces.
+    Light,
+    /// Light text on dark surfaces.
+    Dark,
+}
+
+#[cfg(feature = "gpui")]
+impl ThemeAppearance {
     fn mode(self) -> ThemeMode {
         match self {
-            ThemeVariant::Light => ThemeMode::Light,
-            ThemeVariant::Dark | ThemeVariant::HighContrast => ThemeMode::Dark,
+            ThemeAppearance::Light => ThemeMode::Light,
+            ThemeAppearance::Dark => ThemeMode::Dark,
         }
     }
 }
@@ -50,6 +177,45 @@ pub enum ThemeError {
     /// Raised when the variant name is unknown.
     #[error("unknown theme variant '{0}'")]
     UnknownVariant(String),
+    /// Raised when a custom theme uses an identifier owned by a built-in.
+    #[error("theme id '{0}' is reserved for a built-in theme")]
+    ReservedId(String),
+    /// Raised when a custom theme identifier is not a lowercase slug.
+    #[error("invalid theme id '{0}': use lowercase letters, digits and dashes")]
+    InvalidId(String),
+    /// Raised when a theme file or directory cannot be read.
+    #[error("failed to read theme '{}': {source}", path.display())]
+    Io {
+        /// Path that failed to load.
+        path: PathBuf,
+        /// Underlying IO error.
+        #[source]
+        source: io::Error,
+    },
+    /// Raised when the theme file extension is not a supported format.
+    #[error("unsupported theme file '{}': expected .json or .toml", .0.display())]
+    UnsupportedFormat(PathBuf),
+    /// Raised when a theme document is not valid JSON or TOML.
+    #[error("failed to parse theme '{origin}': {message}")]
+    Parse {
+        /// File path or label describing where the document came from.
+        origin: String,
+        /// Parser error message.
+        message: String,
+    },
+    /// Raised when the theme directory cannot be watched for changes.
+    #[error("failed to watch theme directory: {0}")]
+    Watch(#[from] notify::Error),
+    /// Raised when a token value fails validation.
+    #[error("invalid token '{token}' in theme '{theme}': {reason}")]
+    InvalidToken {
+        /// Identifier of the theme that failed validation.
+        theme: String,
+        /// Dotted path of the offending token, such as `colors.primary`.
+        token: String,
+        /// Human readable description of the problem.
+        reason: String,
+    },
 }
 
 /// Complete definition for a workspace theme variant.
@@ -57,14 +223,33 @@ pub enum ThemeError {
 pub struct ThemeDefinition {
     /// Variant identifier for this definition.
     pub variant: ThemeVariant,
+    /// Whether the theme uses light or dark surfaces.
+    pub appearance: ThemeAppearance,
     /// Generated design tokens associated with the variant.
     pub tokens: DesignTokens,
     /// GPUI theme configuration applied when the variant is active.
+    #[cfg(feature = "gpui")]
     pub config: ThemeConfig,
 }
 
 impl ThemeDefinition {
-    fn new(variant: ThemeVariant, tokens: DesignTokens) -> Self {
+    fn new(variant: ThemeVariant, appearance: ThemeAppearance, tokens: DesignTokens) -> Self {
+        Self {
+            variant,
+            appearance,
+            #[cfg(feature = "gpui")]
+            config: Self::config(variant, appearance, &tokens),
+            tokens,
+        }
+    }
+
+    /// Maps the tokens onto `gpui-component`'s theme configuration.
+    #[cfg(feature = "gpui")]
+    fn config(
+        variant: ThemeVariant,
+        appearance: ThemeAppearance,
+        tokens: &DesignTokens,
+    ) -> ThemeConfig {
         let mut colors = ThemeConfigColors::default();
         let palette = &tokens.colors;
         colors.primary = Some(palette.primary.into());
@@ -85,43 +270,156 @@ impl ThemeDefinition {
         colors.warning = Some(palette.warning.into());
         colors.list_active = Some(palette.accent.into());
         colors.list_active_border = Some(palette.on_accent.into());
+        colors.secondary = Some(palette.muted.into());
+        colors.secondary_foreground = Some(palette.on_muted.into());
+        colors.selection = Some(palette.selection.into());
+        colors.ring = Some(palette.ring.into());
+        colors.caret = Some(palette.ring.into());
+        colors.input = Some(palette.input_border.into());
+        colors.scrollbar_thumb = Some(palette.scrollbar_thumb.into());
+        colors.title_bar = Some(palette.tab_bar.into());
+        colors.title_bar_border = Some(palette.surface_border.into());
+        colors.tab_bar = Some(palette.tab_bar.into());
+        colors.tab = Some(palette.tab_bar.into());
+        colors.tab_foreground = Some(palette.on_muted.into());
+        colors.tab_active = Some(palette.tab_active.into());
+        colors.tab_active_foreground = Some(palette.primary.into());
+        colors.sidebar = Some(palette.sidebar.into());
+        colors.sidebar_foreground = Some(palette.on_sidebar.into());
+        colors.sidebar_border = Some(palette.sidebar_border.into());
+        colors.sidebar_accent = Some(palette.muted.into());
+        colors.sidebar_accent_foreground = Some(palette.on_muted.into());
+        colors.sidebar_primary = Some(palette.primary.into());
+        colors.sidebar_primary_foreground = Some(palette.on_primary.into());
+        colors.table = Some(palette.surface.into());
+        colors.table_head = Some(palette.tab_bar.into());
+        colors.table_hover = Some(palette.muted.into());
+        colors.table_row_border = Some(palette.surface_border.into());
+        colors.accordion = Some(palette.surface.into());
+        colors.accordion_hover = Some(palette.muted.into());
+        colors.progress_bar = Some(palette.primary.into());
+        colors.slider_bar = Some(palette.primary.into());
+        colors.link = Some(palette.primary.into());
+        colors.chart_1 = Some(palette.chart_1.into());
+        colors.chart_2 = Some(palette.chart_2.into());
+        colors.chart_3 = Some(palette.chart_3.into());
+        colors.chart_4 = Some(palette.chart_4.into());
+        colors.chart_5 = Some(palette.chart_5.into());
 
         let typography = &tokens.typography;
         let mut config = ThemeConfig::default();
         config.is_default = matches!(variant, ThemeVariant::Light);
-        config.mode = variant.mode();
+        config.mode = appearance.mode();
         config.name = variant.as_str().into();
         config.font_size = Some(typography.body);
-        config.font_family = Some(typography.font_family.into());
+        config.font_family = Some(typography.font_family.clone().into());
         config.radius = Some(8);
         config.radius_lg = Some(16);
         config.shadow = Some(true);
         config.colors = colors;
+        config
+    }
 
-        Self {
-            variant,
-            tokens,
-            config,
-        }
+    /// Returns `to` with its palette moved `amount` of the way from `from`'s.
+    #[cfg(feature = "gpui")]
+    pub(crate) fn blend(from: &Self, to: &Self, amount: f32) -> Self {
+        let mut tokens = to.tokens.clone();
+        tokens.colors = from.tokens.colors.mix(&to.tokens.colors, amount);
+        Self::new(to.variant, to.appearance, tokens)
     }
 }
 
+/// Theme switch reported to [`ThemeRegistry::observe`] listeners.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ThemeChange {
+    /// Theme that was active before the switch.
+    pub previous: ThemeVariant,
+    /// Theme that is active now.
+    pub current: ThemeVariant,
+}
+
+#[cfg(feature = "gpui")]
+type ThemeListener = Arc<dyn Fn(ThemeChange, &mut App) + Send + Sync>;
+
+/// GPUI global holding the definition most recently applied by a
+/// [`ThemeRegistry`].
+#[cfg(feature = "gpui")]
+struct ActiveDefinition(Arc<ThemeDefinition>);
+
+#[cfg(feature = "gpui")]
+impl Global for ActiveDefinition {}
+
+/// Read access to the design tokens of the active theme.
+///
+/// Implemented for [`App`] so render code can call `cx.design_tokens()`
+/// next to `gpui-component`'s `cx.theme()`.
+#[cfg(feature = "gpui")]
+pub trait ActiveTokens {
+    /// Returns the tokens last loaded by [`ThemeRegistry::apply`] or
+    /// [`ThemeRegistry::scope_window`], or the light tokens when no theme has
+    /// been applied yet.
+    fn design_tokens(&self) -> &DesignTokens;
+}
+
+#[cfg(feature = "gpui")]
+impl ActiveTokens for App {
+    fn design_tokens(&self) -> &DesignTokens {
+        static FALLBACK: Lazy<DesignTokens> = Lazy::new(light_tokens);
+
+        self.try_global::<ActiveDefinition>()
+            .map_or(&*FALLBACK, |active| &active.0.tokens)
+    }
+}
+
+#[derive(Clone)]
+struct ThemeTable {
+    definitions: HashMap<ThemeVariant, Arc<ThemeDefinition>>,
+    order: Vec<ThemeVariant>,
+    /// Unresolved documents of the themes loaded from files, kept so themes
+    /// that extend a reloaded base can be resolved again.
+    sources: HashMap<ThemeVariant, ThemeFile>,
+}
+
 struct ThemeRegistryInner {
-    definitions: HashMap<ThemeVariant, ThemeDefinition>,
+    themes: RwLock<ThemeTable>,
     active: Mutex<ThemeVariant>,
-    order: Vec<ThemeVariant>,
+    preference: Mutex<ThemePreference>,
+    appearance: AppearanceSource,
+    #[cfg(feature = "gpui")]
+    window_overrides: Mutex<HashMap<WindowId, ThemeVariant>>,
+    /// Windows whose root view calls [`ThemeRegistry::scope_window`].
+    #[cfg(feature = "gpui")]
+    scoped_windows: Mutex<HashSet<WindowId>>,
+    /// Definition shown by windows without an override, which differs from
+    /// the active theme's while a transition is running.
+    #[cfg(feature = "gpui")]
+    displayed: Mutex<Arc<ThemeDefinition>>,
+    transition: Mutex<ThemeTransition>,
+    /// Bumped whenever a theme is applied so stale transitions stop.
+    #[cfg(feature = "gpui")]
+    transition_epoch: AtomicU64,
+    #[cfg(feature = "gpui")]
+    listeners: Mutex<Vec<(u64, ThemeListener)>>,
+    #[cfg(feature = "gpui")]
+    next_listener: AtomicU64,
 }
 
 impl ThemeRegistryInner {
-    fn definition(&self, variant: ThemeVariant) -> &ThemeDefinition {
-        self.definitions
+    fn definition(&self, variant: ThemeVariant) -> Option<Arc<ThemeDefinition>> {
+        self.themes
+            .read()
+            .expect("theme table poisoned")
+            .definitions
             .get(&variant)
-            .expect("theme definition registered")
+            .cloned()
     }
 }
 
 /// State manager that tracks the currently active theme and provides helpers
 /// for mutating GPUI's global theme instance.
+///
+/// Clones share the same set of registered themes, so a theme loaded through
+/// one handle is visible to every window holding a clone.
 #[derive(Clone)]
 pub struct ThemeRegistry {
     inner: Arc<ThemeRegistryInner>,
@@ -129,32 +427,45 @@ pub struct ThemeRegistry {
 
 impl Default for ThemeRegistry {
     fn default() -> Self {
-        static BUILT_INS: Lazy<HashMap<ThemeVariant, ThemeDefinition>> = Lazy::new(|| {
-            let mut map = HashMap::new();
-            map.insert(
-                ThemeVariant::Light,
-                ThemeDefinition::new(ThemeVariant::Light, light_tokens()),
-            );
-            map.insert(
-                ThemeVariant::Dark,
-                ThemeDefinition::new(ThemeVariant::Dark, dark_tokens()),
-            );
-            map.insert(
-                ThemeVariant::HighContrast,
-                ThemeDefinition::new(ThemeVariant::HighContrast, high_contrast_tokens()),
-            );
-            map
+        static BUILT_INS: Lazy<ThemeTable> = Lazy::new(|| {
+            let definitions = [
+                ThemeDefinition::new(ThemeVariant::Light, ThemeAppearance::Light, light_tokens()),
+                ThemeDefinition::new(ThemeVariant::Dark, ThemeAppearance::Dark, dark_tokens()),
+                ThemeDefinition::new(
+                    ThemeVariant::HighContrast,
+                    ThemeAppearance::Dark,
+                    high_contrast_tokens(),
+                ),
+            ];
+            ThemeTable {
+                definitions: definitions
+                    .into_iter()
+                    .map(|definition| (definition.variant, Arc::new(definition)))
+                    .collect(),
+                order: ThemeVariant::BUILT_INS.to_vec(),
+                sources: HashMap::new(),
+            }
         });
 
         Self {
             inner: Arc::new(ThemeRegistryInner {
-                definitions: BUILT_INS.clone(),
+                themes: RwLock::new(BUILT_INS.clone()),
                 active: Mutex::new(ThemeVariant::Light),
-                order: vec![
-                    ThemeVariant::Light,
-                    ThemeVariant::Dark,
-                    ThemeVariant::HighContrast,
-                ],
+                preference: Mutex::new(ThemePreference::default()),
+                appearance: AppearanceSource::default(),
+                #[cfg(feature = "gpui")]
+                window_overrides: Mutex::default(),
+                #[cfg(feature = "gpui")]
+                scoped_windows: Mutex::default(),
+                #[cfg(feature = "gpui")]
+                displayed: Mutex::new(BUILT_INS.definitions[&ThemeVariant::Light].clone()),
+                transition: Mutex::new(ThemeTransition::default()),
+                #[cfg(feature = "gpui")]
+                transition_epoch: AtomicU64::new(0),
+                #[cfg(feature = "gpui")]
+                listeners: Mutex::default(),
+                #[cfg(feature = "gpui")]
+                next_listener: AtomicU64::new(0),
             }),
         }
     }
@@ -173,23 +484,92 @@ impl ThemeRegistry {
         *self.inner.active.lock().expect("theme mutex poisoned")
     }
 
-    /// Iterates over the theme definitions in a stable order.
-    pub fn definitions(&self) -> impl Iterator<Item = &ThemeDefinition> {
-        self.inner
+    /// Returns how the active theme is chosen.
+    #[must_use]
+    pub fn preference(&self) -> ThemePreference {
+        *self.inner.preference.lock().expect("theme mutex poisoned")
+    }
+
+    /// Returns the system appearance shared by every clone of the registry.
+    #[must_use]
+    pub fn appearance_source(&self) -> AppearanceSource {
+        self.inner.appearance.clone()
+    }
+
+    #[cfg(any(test, feature = "gpui"))]
+    pub(crate) fn store_preference(&self, preference: ThemePreference) {
+        *self.inner.preference.lock().expect("theme mutex poisoned") = preference;
+    }
+
+    /// Returns how [`ThemeRegistry::apply`] moves between themes.
+    #[must_use]
+    pub fn transition(&self) -> ThemeTransition {
+        *self.inner.transition.lock().expect("theme mutex poisoned")
+    }
+
+    /// Sets how later theme changes are presented. A running transition
+    /// finishes with the settings it started with.
+    pub fn set_transition(&self, transition: ThemeTransition) {
+        *self.inner.transition.lock().expect("theme mutex poisoned") = transition;
+    }
+
+    /// Iterates over the theme definitions in a stable order: the built-ins
+    /// first, followed by custom themes in registration order.
+    pub fn definitions(&self) -> impl Iterator<Item = Arc<ThemeDefinition>> {
+        let themes = self.inner.themes.read().expect("theme table poisoned");
+        themes
             .order
             .iter()
-            .map(|variant| self.definition(*variant))
+            .map(|variant| themes.definitions[variant].clone())
+            .collect::<Vec<_>>()
+            .into_iter()
     }
 
     /// Returns the definition for the requested variant.
+    ///
+    /// A custom variant that was never registered with this registry, such
+    /// as one read from settings before its theme file loaded, gets the light
+    /// theme; use [`ThemeRegistry::contains`] to tell the two apart.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the theme table lock was poisoned.
     #[must_use]
-    pub fn definition(&self, variant: ThemeVariant) -> &ThemeDefinition {
-        self.inner.definition(variant)
+    pub fn definition(&self, variant: ThemeVariant) -> Arc<ThemeDefinition> {
+        let themes = self.inner.themes.read().expect("theme table poisoned");
+        themes
+            .definitions
+            .get(&variant)
+            .unwrap_or(&themes.definitions[&ThemeVariant::Light])
+            .clone()
     }
 
-    /// Looks up a variant by its slug.
+    /// Returns `variant`, or the light theme when it is not registered.
+    #[cfg(feature = "gpui")]
+    fn registered_or_light(&self, variant: ThemeVariant) -> ThemeVariant {
+        if self.contains(variant) {
+            variant
+        } else {
+            ThemeVariant::Light
+        }
+    }
+
+    /// Returns `true` when a definition exists for `variant`.
+    #[must_use]
+    pub fn contains(&self, variant: ThemeVariant) -> bool {
+        self.inner.definition(variant).is_some()
+    }
+
+    /// Looks up a variant by its slug. The built-ins are also found by the
+    /// variant names earlier releases persisted, such as `"HighContrast"`.
     pub fn variant_from_str(&self, name: &str) -> Result<ThemeVariant, ThemeError> {
+        if let Some(variant) = ThemeVariant::built_in_from_str(name) {
+            return Ok(variant);
+        }
         self.inner
+            .themes
+            .read()
+            .expect("theme table poisoned")
             .order
             .iter()
             .copied()
@@ -197,53 +577,480 @@ impl ThemeRegistry {
             .ok_or_else(|| ThemeError::UnknownVariant(name.to_owned()))
     }
 
-    /// Applies the variant and updates the GPUI theme globals.
+    /// Registers a custom theme under `id`, replacing any custom theme that
+    /// previously used the same identifier.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ThemeError::ReservedId`] for built-in slugs and
+    /// [`ThemeError::InvalidId`] for identifiers that are not lowercase slugs.
+    pub fn register(
+        &self,
+        id: &str,
+        appearance: ThemeAppearance,
+        tokens: DesignTokens,
+    ) -> Result<ThemeVariant, ThemeError> {
+        self.insert_custom(id, appearance, tokens, None)
+    }
+
+    fn insert_custom(
+        &self,
+        id: &str,
+        appearance: ThemeAppearance,
+        tokens: DesignTokens,
+        source: Option<ThemeFile>,
+    ) -> Result<ThemeVariant, ThemeError> {
+        if ThemeVariant::BUILT_INS
+            .iter()
+            .any(|variant| variant.as_str() == id)
+        {
+            return Err(ThemeError::ReservedId(id.to_owned()));
+        }
+        let valid = !id.is_empty()
+            && id
+                .chars()
+                .all(|ch| ch.is_ascii_lowercase() || ch.is_ascii_digit() || ch == '-');
+        if !valid {
+            return Err(ThemeError::InvalidId(id.to_owned()));
+        }
+
+        let variant = ThemeVariant::Custom(ThemeId::new(id));
+        let definition = Arc::new(ThemeDefinition::new(variant, appearance, tokens));
+        let mut themes = self.inner.themes.write().expect("theme table poisoned");
+        if themes.definitions.insert(variant, definition).is_none() {
+            themes.order.push(variant);
+        }
+        match source {
+            Some(source) => themes.sources.insert(variant, source),
+            None => themes.sources.remove(&variant),
+        };
+        Ok(variant)
+    }
+
+    /// Parses, validates and registers a theme document.
+    ///
+    /// Useful for themes embedded with `include_str!`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error when the document fails to parse, a token fails
+    /// validation, or the identifier is rejected by
+    /// [`ThemeRegistry::register`].
+    pub fn load_str(&self, source: &str, format: ThemeFormat) -> Result<ThemeVariant, ThemeError> {
+        self.register_file(ThemeFile::parse(source, format, "<inline>")?)
+    }
+
+    /// Loads and registers a single `.json` or `.toml` theme file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error when the file cannot be read or fails the same checks
+    /// as [`ThemeRegistry::load_str`].
+    pub fn load_file(&self, path: impl AsRef<Path>) -> Result<ThemeVariant, ThemeError> {
+        self.register_file(ThemeFile::read(path.as_ref())?)
+    }
+
+    /// Loads every `.json` and `.toml` theme in `dir`, sorted by file name.
+    ///
+    /// Themes that `extends` another theme in the same directory are
+    /// registered after their base. Stops at the first file that fails to
+    /// load so broken themes are not silently skipped.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error raised while listing the directory or loading a
+    /// theme file.
+    pub fn load_dir(&self, dir: impl AsRef<Path>) -> Result<Vec<ThemeVariant>, ThemeError> {
+        let dir = dir.as_ref();
+        let io_error = |source| ThemeError::Io {
+            path: dir.to_path_buf(),
+            source,
+        };
+        let mut paths = Vec::new();
+        for entry in fs::read_dir(dir).map_err(io_error)? {
+            let path = entry.map_err(io_error)?.path();
+            if path.is_file() && ThemeFormat::from_path(&path).is_some() {
+                paths.push(path);
+            }
+        }
+        paths.sort();
+
+        let pending = paths
+            .iter()
+            .map(|path| ThemeFile::read(path))
+            .collect::<Result<Vec<_>, _>>()?;
+        self.register_in_order(pending)
+    }
+
+    /// Registers `pending` so that a theme extending another pending theme
+    /// comes after its base, keeping the given order otherwise.
+    fn register_in_order(
+        &self,
+        mut pending: Vec<ThemeFile>,
+    ) -> Result<Vec<ThemeVariant>, ThemeError> {
+        let mut loaded = Vec::with_capacity(pending.len());
+        while !pending.is_empty() {
+            let waiting_on_base = |file: &ThemeFile| {
+                file.extends
+                    .as_deref()
+                    .is_some_and(|base| pending.iter().any(|other| other.id == base))
+            };
+            // A cycle leaves every file waiting; registering the first one
+            // then reports its missing base.
+            let next = pending
+                .iter()
+                .position(|file| !waiting_on_base(file))
+                .unwrap_or(0);
+            loaded.push(self.register_file(pending.remove(next))?);
+        }
+        Ok(loaded)
+    }
+
+    fn register_file(&self, file: ThemeFile) -> Result<ThemeVariant, ThemeError> {
+        let base = match file.extends.as_deref() {
+            Some(base) => Some(self.definition(self.variant_from_str(base)?)),
+            None => None,
+        };
+        let (id, source) = (file.id.clone(), file.clone());
+        let (appearance, tokens) = file.resolve(base.as_deref())?;
+        self.insert_custom(&id, appearance, tokens, Some(source))
+    }
+
+    /// Returns the file-backed themes that extend `variant`, directly or
+    /// through one another, with every base before the themes extending it.
+    #[cfg(any(test, feature = "gpui"))]
+    pub(crate) fn dependents(&self, variant: ThemeVariant) -> Vec<ThemeVariant> {
+        let themes = self.inner.themes.read().expect("theme table poisoned");
+        let mut found = vec![variant];
+        let mut next = 0;
+        while let Some(base) = found.get(next).copied() {
+            next += 1;
+            for candidate in &themes.order {
+                let extends_base = themes
+                    .sources
+                    .get(candidate)
+                    .and_then(|source| source.extends.as_deref())
+                    == Some(base.as_str());
+                if extends_base && !found.contains(candidate) {
+                    found.push(*candidate);
+                }
+            }
+        }
+        found.remove(0);
+        found
+    }
+
+    /// Resolves `variants` again from their files against the current
+    /// definitions of their bases, in the order [`ThemeRegistry::load_dir`]
+    /// registers a directory.
+    #[cfg(any(test, feature = "gpui"))]
+    pub(crate) fn re_resolve(&self, variants: &[ThemeVariant]) -> Result<(), ThemeError> {
+        let pending = {
+            let themes = self.inner.themes.read().expect("theme table poisoned");
+            variants
+                .iter()
+                .filter_map(|variant| themes.sources.get(variant).cloned())
+                .collect()
+        };
+        self.register_in_order(pending).map(drop)
+    }
+
+    /// Applies the variant, updates the GPUI theme globals and refreshes every
+    /// window.
+    ///
+    /// Listeners registered with [`ThemeRegistry::observe`] are notified when
+    /// the active variant changes.
+    ///
+    /// With an animated [`ThemeRegistry::transition`],
+    /// [`ThemeRegistry::active`] reports `variant` straight away while the
+    /// palette fades towards it in every window that follows the app-wide
+    /// theme.
+    ///
+    /// A custom variant that is not registered applies the light theme
+    /// instead.
+    #[cfg(feature = "gpui")]
     pub fn apply(&self, variant: ThemeVariant, cx: &mut App) {
+        let variant = self.registered_or_light(variant);
+        let target = self.definition(variant);
+        let previous = std::mem::replace(
+            &mut *self.inner.active.lock().expect("theme mutex poisoned"),
+            variant,
+        );
+
+        let epoch = self.begin_transition();
+        let transition = self.transition();
+        let from = self.displayed();
+        if transition.is_animated()
+            && cx.has_global::<ActiveDefinition>()
+            && !Arc::ptr_eq(&from, &target)
+        {
+            self.animate(epoch, &from, target, transition, cx);
+        } else {
+            self.show(target, cx);
+        }
+        cx.refresh_windows();
+
+        if previous != variant {
+            self.notify_listeners(
+                ThemeChange {
+                    previous,
+                    current: variant,
+                },
+                cx,
+            );
+        }
+    }
+
+    /// Calls `listener` with the previous and new variant whenever the active
+    /// theme changes, until the returned subscription is dropped.
+    ///
+    /// Listeners run once the current update finishes, so they may update the
+    /// view that applied the theme. Per-window overrides set with
+    /// [`ThemeRegistry::set_window_theme`] do not notify listeners.
+    #[cfg(feature = "gpui")]
+    pub fn observe(
+        &self,
+        listener: impl Fn(ThemeChange, &mut App) + Send + Sync + 'static,
+    ) -> Subscription {
+        let id = self.inner.next_listener.fetch_add(1, Ordering::Relaxed);
+        self.inner
+            .listeners
+            .lock()
+            .expect("theme mutex poisoned")
+            .push((id, Arc::new(listener)));
+
+        let inner = Arc::downgrade(&self.inner);
+        Subscription::new(move || {
+            if let Some(inner) = inner.upgrade() {
+                inner
+                    .listeners
+                    .lock()
+                    .expect("theme mutex poisoned")
+                    .retain(|(listener_id, _)| *listener_id != id);
+            }
+        })
+    }
+
+    #[cfg(feature = "gpui")]
+    fn notify_listeners(&self, change: ThemeChange, cx: &mut App) {
+        let listeners: Vec<ThemeListener> = self
+            .inner
+            .listeners
+            .lock()
+            .expect("theme mutex poisoned")
+            .iter()
+            .map(|(_, listener)| listener.clone())
+            .collect();
+        if listeners.is_empty() {
+            return;
+        }
+        cx.defer(move |cx| {
+            for listener in listeners {
+                listener(change, cx);
+            }
+        });
+    }
+
+    /// Invalidates any running transition and returns the new epoch.
+    #[cfg(feature = "gpui")]
+    fn begin_transition(&self) -> u64 {
+        self.inner.transition_epoch.fetch_add(1, Ordering::SeqCst) + 1
+    }
+
+    /// Returns `true` while no theme has been applied since `epoch` began.
+    #[cfg(feature = "gpui")]
+    pub(crate) fn is_current_transition(&self, epoch: u64) -> bool {
+        self.inner.transition_epoch.load(Ordering::SeqCst) == epoch
+    }
+
+    #[cfg(feature = "gpui")]
+    fn displayed(&self) -> Arc<ThemeDefinition> {
+        self.inner
+            .displayed
+            .lock()
+            .expect("theme mutex poisoned")
+            .clone()
+    }
+
+    /// Shows `definition` in every window that follows the app-wide theme.
+    #[cfg(feature = "gpui")]
+    pub(crate) fn show(&self, definition: Arc<ThemeDefinition>, cx: &mut App) {
+        *self.inner.displayed.lock().expect("theme mutex poisoned") = definition.clone();
+        self.install_definition(definition, cx);
+    }
+
+    /// Pins `window` to `variant` regardless of the app-wide theme, or
+    /// releases it back to the app-wide theme when `variant` is `None`.
+    ///
+    /// The window is refreshed so the change shows on its next frame; see
+    /// [`ThemeRegistry::scope_window`]. A custom variant that is not
+    /// registered pins the light theme.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the theme mutex was poisoned, and in debug builds when
+    /// pinning a window whose root view does not call
+    /// [`ThemeRegistry::scope_window`], since the pin would never show.
+    #[cfg(feature = "gpui")]
+    pub fn set_window_theme(&self, window: &mut Window, variant: Option<ThemeVariant>) {
+        debug_assert!(
+            variant.is_none() || self.is_window_scoped(window),
+            "pinned a window whose root view does not call `ThemeRegistry::scope_window`"
+        );
+        let variant = variant.map(|variant| self.registered_or_light(variant));
+        let id = window.window_handle().window_id();
+        let mut overrides = self
+            .inner
+            .window_overrides
+            .lock()
+            .expect("theme mutex poisoned");
+        match variant {
+            Some(variant) => overrides.insert(id, variant),
+            None => overrides.remove(&id),
+        };
+        drop(overrides);
+        window.refresh();
+    }
+
+    /// Returns the theme `window` renders with: its override, or the active
+    /// theme.
+    #[cfg(feature = "gpui")]
+    #[must_use]
+    pub fn window_theme(&self, window: &Window) -> ThemeVariant {
+        self.window_override(window)
+            .unwrap_or_else(|| self.active())
+    }
+
+    #[cfg(feature = "gpui")]
+    fn window_override(&self, window: &Window) -> Option<ThemeVariant> {
+        self.inner
+            .window_overrides
+            .lock()
+            .expect("theme mutex poisoned")
+            .get(&window.window_handle().window_id())
+            .copied()
+    }
+
+    /// Returns `true` once `window`'s root view has called
+    /// [`ThemeRegistry::scope_window`], so it can be pinned.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the theme mutex was poisoned.
+    #[cfg(feature = "gpui")]
+    #[must_use]
+    pub fn is_window_scoped(&self, window: &Window) -> bool {
+        self.inner
+            .scoped_windows
+            .lock()
+            .expect("theme mutex poisoned")
+            .contains(&window.window_handle().window_id())
+    }
+
+    /// Loads `window`'s theme into the GPUI globals.
+    ///
+    /// GPUI keeps a single global [`Theme`], so every window sharing the
+    /// registry calls this first thing in its root view's `render`. Windows
+    /// draw one at a time, which lets each paint with its own variant while
+    /// several are on screen.
+    ///
+    /// A pinned window's theme is only loaded while that window draws: the
+    /// app-wide theme is put back once the draw finishes, so other windows,
+    /// deferred callbacks, and global observers never see the pin. Windows
+    /// without an override pick up the current frame of a running
+    /// transition; overrides always switch instantly.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the theme mutex was poisoned.
+    #[cfg(feature = "gpui")]
+    pub fn scope_window(&self, window: &Window, cx: &mut App) {
+        self.inner
+            .scoped_windows
+            .lock()
+            .expect("theme mutex poisoned")
+            .insert(window.window_handle().window_id());
+        let displayed = self.displayed();
+        let definition = self
+            .window_override(window)
+            .map_or_else(|| displayed.clone(), |variant| self.definition(variant));
+        self.load(definition.clone(), cx);
+        if !Arc::ptr_eq(&definition, &displayed) {
+            // Windows draw inside an update, so this runs right after the
+            // frame is painted.
+            let registry = self.clone();
+            cx.defer(move |cx| registry.load(registry.displayed(), cx));
+        }
+    }
+
+    /// Installs `definition` unless it is already loaded.
+    #[cfg(feature = "gpui")]
+    fn load(&self, definition: Arc<ThemeDefinition>, cx: &mut App) {
+        let loaded = cx
+            .try_global::<ActiveDefinition>()
+            .is_some_and(|active| Arc::ptr_eq(&active.0, &definition));
+        if !loaded {
+            self.install_definition(definition, cx);
+        }
+    }
+
+    #[cfg(feature = "gpui")]
+    fn install_definition(&self, definition: Arc<ThemeDefinition>, cx: &mut App) {
         if !cx.has_global::<Theme>() {
             gpui_component::theme::init(cx);
         }
 
         let light = Rc::new(self.definition(ThemeVariant::Light).config.clone());
         let dark = Rc::new(self.definition(ThemeVariant::Dark).config.clone());
-        let high_contrast = Rc::new(self.definition(ThemeVariant::HighContrast).config.clone());
+        let selected = Rc::new(definition.config.clone());
 
         let theme = Theme::global_mut(cx);
-        theme.light_theme = light.clone();
-        theme.dark_theme = dark.clone();
-
-        let selected = match variant {
-            ThemeVariant::Light => light,
-            ThemeVariant::Dark => dark,
-            ThemeVariant::HighContrast => high_contrast,
-        };
+        theme.light_theme = light;
+        theme.dark_theme = dark;
 
         theme.apply_config(&selected);
-        theme.mode = variant.mode();
+        theme.mode = definition.appearance.mode();
         theme.shadow = true;
-        theme.font_size = px(self.definition(variant).tokens.typography.body);
+        theme.font_size = px(definition.tokens.typography.body);
+        cx.set_global(ActiveDefinition(definition));
+    }
 
-        *self.inner.active.lock().expect("theme mutex poisoned") = variant;
+    /// Returns the theme that follows `variant` in registration order,
+    /// wrapping around to the first theme.
+    #[must_use]
+    pub fn next_variant(&self, variant: ThemeVariant) -> ThemeVariant {
+        let themes = self.inner.themes.read().expect("theme table poisoned");
+        let position = themes
+            .order
+            .iter()
+            .position(|candidate| *candidate == variant)
+            .map_or(0, |index| index + 1);
+        themes.order[position % themes.order.len()]
     }
 
-    /// Convenience helper to cycle between the built-in variants.
+    /// Convenience helper to cycle through every registered variant.
+    #[cfg(feature = "gpui")]
     pub fn cycle(&self, cx: &mut App) {
-        let next = match self.active() {
-            ThemeVariant::Light => ThemeVariant::Dark,
-            ThemeVariant::Dark => ThemeVariant::HighContrast,
-            ThemeVariant::HighContrast => ThemeVariant::Light,
-        };
+        let next = self.next_variant(self.active());
         self.apply(next, cx);
     }
 
-    /// Applies the default (light) theme.
+    /// Applies the theme selected by [`ThemeRegistry::preference`], which is
+    /// the light theme unless a preference was set.
+    ///
+    /// The platform's current appearance is recorded first, so a
+    /// [`ThemePreference::System`] preference starts on the matching theme.
+    #[cfg(feature = "gpui")]
     pub fn install(&self, cx: &mut App) {
-        self.apply(ThemeVariant::Light, cx);
+        self.inner.appearance.set(cx.window_appearance().into());
+        self.apply(self.preferred_variant(), cx);
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::color::Color;
 
     #[test]
     fn variant_lookup_by_name() {
@@ -252,6 +1059,10 @@ mod tests {
             registry.variant_from_str("dark").unwrap(),
             ThemeVariant::Dark
         );
+        assert_eq!(
+            registry.variant_from_str("HighContrast").unwrap(),
+            ThemeVariant::HighContrast
+        );
         assert!(matches!(
             registry.variant_from_str("missing"),
             Err(ThemeError::UnknownVariant(name)) if name == "missing"
@@ -264,4 +1075,192 @@ mod tests {
         let definition = registry.definition(ThemeVariant::HighContrast);
         assert!(definition.tokens.typography.body > 16.0);
     }
+
+    #[test]
+    #[cfg(feature = "gpui")]
+    fn custom_themes_join_the_registry() {
+        let registry = ThemeRegistry::new();
+        let variant = registry
+            .register("brand", ThemeAppearance::Dark, dark_tokens())
+            .unwrap();
+        assert_eq!(registry.variant_from_str("brand").unwrap(), variant);
+        assert_eq!(registry.definitions().count(), 4);
+        assert_eq!(registry.next_variant(variant), ThemeVariant::Light);
+        assert_eq!(registry.definition(variant).config.mode, ThemeMode::Dark);
+
+        let clone = registry.clone();
+        clone
+            .register("brand", ThemeAppearance::Light, light_tokens())
+            .unwrap();
+        assert_eq!(registry.definitions().count(), 4);
+        assert_eq!(
+            registry.definition(variant).appearance,
+            ThemeAppearance::Light
+        );
+    }
+
+    #[test]
+    fn built_in_ids_are_reserved() {
+        let registry = ThemeRegistry::new();
+        assert!(matches!(
+            registry.register("dark", ThemeAppearance::Dark, dark_tokens()),
+            Err(ThemeError::ReservedId(_))
+        ));
+        assert!(matches!(
+            registry.register("Brand Theme", ThemeAppearance::Dark, dark_tokens()),
+            Err(ThemeError::InvalidId(_))
+        ));
+    }
+
+    #[test]
+    fn failed_reload_keeps_last_good_theme() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("brand.json");
+        let palette = serde_json::to_value(&dark_tokens().colors).unwrap();
+        let typography = serde_json::to_value(&dark_tokens().typography).unwrap();
+        let document = serde_json::json!({
+            "id": "brand",
+            "appearance": "dark",
+            "colors": palette,
+            "typography": typography,
+        });
+        fs::write(&path, document.to_string()).unwrap();
+
+        let registry = ThemeRegistry::new();
+        assert_eq!(registry.load_dir(dir.path()).unwrap().len(), 1);
+        let variant = registry.load_file(&path).unwrap();
+
+        fs::write(&path, document.to_string().replace("#60a5fa", "blue")).unwrap();
+        assert!(matches!(
+            registry.load_file(&path),
+            Err(ThemeError::InvalidToken { .. })
+        ));
+        assert_eq!(
+            registry.definition(variant).tokens.colors.primary,
+            Color::hex("#60a5fa")
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "gpui")]
+    fn blend_moves_only_the_palette() {
+        let registry = ThemeRegistry::new();
+        let light = registry.definition(ThemeVariant::Light);
+        let contrast = registry.definition(ThemeVariant::HighContrast);
+
+        let start = ThemeDefinition::blend(&light, &contrast, 0.0);
+        assert_eq!(start.tokens.colors, light.tokens.colors);
+        assert_eq!(start.tokens.typography, contrast.tokens.typography);
+        assert_eq!(start.variant, ThemeVariant::HighContrast);
+
+        let end = ThemeDefinition::blend(&light, &contrast, 1.0);
+        assert_eq!(end.tokens.colors, contrast.tokens.colors);
+    }
+
+    #[test]
+    #[cfg(feature = "gpui")]
+    fn component_slots_follow_the_palette() {
+        let registry = ThemeRegistry::new();
+        let dark = registry.definition(ThemeVariant::Dark);
+        let (slots, palette) = (&dark.config.colors, &dark.tokens.colors);
+        assert_eq!(slots.sidebar, Some(palette.sidebar.into()));
+        assert_eq!(slots.ring, Some(palette.ring.into()));
+        assert_eq!(slots.input, Some(palette.input_border.into()));
+        assert_eq!(slots.chart_5, Some(palette.chart_5.into()));
+    }
+
+    #[test]
+    #[cfg(feature = "gpui")]
+    fn dropping_subscription_removes_listener() {
+        let registry = ThemeRegistry::new();
+        let subscription = registry.clone().observe(|_, _| {});
+        assert_eq!(registry.inner.listeners.lock().unwrap().len(), 1);
+        drop(subscription);
+        assert!(registry.inner.listeners.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn load_dir_registers_bases_first() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(
+            dir.path().join("a-child.toml"),
+            "id = \"child\"\nextends = \"parent\"\n[colors]\nprimary = \"#7c3aed\"\n",
+        )
+        .unwrap();
+        fs::write(
+            dir.path().join("b-parent.toml"),
+            "id = \"parent\"\nextends = \"dark\"\n[typography]\nfont_family = \"Inter\"\n",
+        )
+        .unwrap();
+
+        let registry = ThemeRegistry::new();
+        let loaded = registry.load_dir(dir.path()).unwrap();
+        let child = registry.variant_from_str("child").unwrap();
+        assert_eq!(loaded.last(), Some(&child));
+
+        let definition = registry.definition(child);
+        assert_eq!(definition.appearance, ThemeAppearance::Dark);
+        assert_eq!(definition.tokens.colors.primary, Color::hex("#7c3aed"));
+        assert_eq!(definition.tokens.typography.font_family, "Inter");
+        assert_eq!(definition.tokens.colors.accent, dark_tokens().colors.accent);
+    }
+
+    #[test]
+    fn dependents_follow_a_reloaded_base() {
+        let registry = ThemeRegistry::new();
+        let base = |primary: &str| {
+            format!("id = \"parent\"\nextends = \"dark\"\n[colors]\nprimary = \"{primary}\"\n")
+        };
+        registry
+            .load_str(&base("#7c3aed"), ThemeFormat::Toml)
+            .unwrap();
+        registry
+            .load_str("id = \"child\"\nextends = \"parent\"\n", ThemeFormat::Toml)
+            .unwrap();
+        registry
+            .load_str(
+                "id = \"grandchild\"\nextends = \"child\"\n",
+                ThemeFormat::Toml,
+            )
+            .unwrap();
+        let parent = registry.variant_from_str("parent").unwrap();
+        let child = registry.variant_from_str("child").unwrap();
+        let grandchild = registry.variant_from_str("grandchild").unwrap();
+        assert_eq!(registry.dependents(parent), vec![child, grandchild]);
+
+        registry
+            .load_str(&base("#f472b6"), ThemeFormat::Toml)
+            .unwrap();
+        registry.re_resolve(&registry.dependents(parent)).unwrap();
+        assert_eq!(
+            registry.definition(grandchild).tokens.colors.primary,
+            Color::hex("#f472b6")
+        );
+    }
+
+    #[test]
+    fn custom_variant_serializes_as_slug() {
+        for (variant, json) in [
+            (ThemeVariant::Custom(ThemeId::new("brand")), r#""brand""#),
+            (ThemeVariant::HighContrast, r#""high-contrast""#),
+        ] {
+            assert_eq!(serde_json::to_string(&variant).unwrap(), json);
+            let parsed: ThemeVariant = serde_json::from_str(json).unwrap();
+            assert_eq!(parsed, variant);
+        }
+    }
+
+    #[test]
+    fn legacy_and_unregistered_variants_fall_back() {
+        let legacy: ThemeVariant = serde_json::from_str(r#""Dark""#).unwrap();
+        assert_eq!(legacy, ThemeVariant::Dark);
+
+        let registry = ThemeRegistry::new();
+        let unregistered: ThemeVariant = serde_json::from_str(r#""not-loaded""#).unwrap();
+        assert!(!registry.contains(unregistered));
+        assert_eq!(
+            registry.definition(unregistered).variant,
+            ThemeVariant::Light
+        );
+    }
 }
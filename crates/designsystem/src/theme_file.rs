@@ -0,0 +1,588 @@
+//! Theme files that let applications ship custom token sets without touching
+//! the built-in variants.
+
+use std::{fs, path::Path};
+
+use once_cell::sync::Lazy;
+use serde::Deserialize;
+use serde_json::{map::Entry, Map, Value};
+
+use crate::{
+    color::Color,
+    theme::{ThemeAppearance, ThemeDefinition, ThemeError},
+    tokens::{
+        light_tokens, ColorPalette, DesignTokens, ElevationLevel, ElevationScale, SpacingScale,
+        TypographyScale, BASE_ELEVATION, BASE_SPACING,
+    },
+};
+
+/// Serialization formats accepted for theme files.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ThemeFormat {
+    /// JSON documents, typically stored as `*.json`.
+    Json,
+    /// TOML documents, typically stored as `*.toml`.
+    Toml,
+}
+
+impl ThemeFormat {
+    /// Infers the format from the file extension of `path`.
+    #[must_use]
+    pub fn from_path(path: &Path) -> Option<Self> {
+        match path.extension()?.to_str()?.to_ascii_lowercase().as_str() {
+            "json" => Some(Self::Json),
+            "toml" => Some(Self::Toml),
+            _ => None,
+        }
+    }
+}
+
+/// Raw theme document as authored on disk.
+///
+/// Token groups stay untyped until the base theme is known, so a document
+/// that `extends` another theme only needs to list the tokens it changes.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ThemeDocument {
+    id: String,
+    #[serde(default)]
+    extends: Option<String>,
+    #[serde(default)]
+    appearance: Option<ThemeAppearance>,
+    #[serde(default)]
+    colors: Option<Map<String, Value>>,
+    #[serde(default)]
+    typography: Option<Map<String, Value>>,
+    #[serde(default)]
+    spacing: Option<Map<String, Value>>,
+    #[serde(default)]
+    elevations: Option<Map<String, Value>>,
+}
+
+/// Parsed theme document whose token keys have been checked.
+#[derive(Debug, Clone)]
+pub(crate) struct ThemeFile {
+    pub(crate) id: String,
+    /// Slug of the theme whose tokens this document overrides.
+    pub(crate) extends: Option<String>,
+    appearance: Option<ThemeAppearance>,
+    tokens: Map<String, Value>,
+}
+
+/// Colors are read as strings and parsed in [`into_tokens`] so
+/// errors can name the offending token path.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct PaletteSource {
+    primary: String,
+    on_primary: String,
+    accent: String,
+    on_accent: String,
+    background: String,
+    surface: String,
+    surface_border: String,
+    muted: String,
+    on_muted: String,
+    success: String,
+    warning: String,
+    danger: String,
+    #[serde(default)]
+    selection: Option<String>,
+    #[serde(default)]
+    ring: Option<String>,
+    #[serde(default)]
+    input_border: Option<String>,
+    #[serde(default)]
+    scrollbar_thumb: Option<String>,
+    #[serde(default)]
+    tab_bar: Option<String>,
+    #[serde(default)]
+    tab_active: Option<String>,
+    #[serde(default)]
+    sidebar: Option<String>,
+    #[serde(default)]
+    on_sidebar: Option<String>,
+    #[serde(default)]
+    sidebar_border: Option<String>,
+    #[serde(default)]
+    chart_1: Option<String>,
+    #[serde(default)]
+    chart_2: Option<String>,
+    #[serde(default)]
+    chart_3: Option<String>,
+    #[serde(default)]
+    chart_4: Option<String>,
+    #[serde(default)]
+    chart_5: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct TypographySource {
+    font_family: String,
+    display: f32,
+    headline: f32,
+    title: f32,
+    body: f32,
+    label: f32,
+}
+
+/// Every token path a theme may set, taken from the built-in light theme.
+static TOKEN_SCHEMA: Lazy<Map<String, Value>> = Lazy::new(|| match token_map(&light_tokens()) {
+    Value::Object(schema) => schema,
+    _ => unreachable!("design tokens serialize as a map"),
+});
+
+/// Token paths a standalone theme must declare: the schema without the
+/// component colors, which are derived from the core palette when omitted.
+static REQUIRED_TOKENS: Lazy<Map<String, Value>> = Lazy::new(|| {
+    let mut schema = TOKEN_SCHEMA.clone();
+    if let Some(Value::Object(colors)) = schema.get_mut("colors") {
+        for token in ColorPalette::COMPONENT_TOKENS {
+            colors.remove(token);
+        }
+    }
+    schema
+});
+
+fn token_map(tokens: &DesignTokens) -> Value {
+    serde_json::to_value(tokens).expect("design tokens serialize to JSON")
+}
+
+impl ThemeFile {
+    /// Parses a theme document and rejects token keys that do not exist.
+    /// `origin` is only used to label errors.
+    pub(crate) fn parse(
+        source: &str,
+        format: ThemeFormat,
+        origin: &str,
+    ) -> Result<Self, ThemeError> {
+        let parse_error = |message: String| ThemeError::Parse {
+            origin: origin.to_owned(),
+            message,
+        };
+        let document: ThemeDocument = match format {
+            ThemeFormat::Json => serde_json::from_str(source).map_err(|err| err.to_string()),
+            ThemeFormat::Toml => toml::from_str(source).map_err(|err| err.to_string()),
+        }
+        .map_err(parse_error)?;
+
+        let groups = [
+            ("colors", document.colors),
+            ("typography", document.typography),
+            ("spacing", document.spacing),
+            ("elevations", document.elevations),
+        ];
+        if document.extends.is_none() {
+            // Without a base, the palette, type scale and appearance have
+            // nothing to fall back on.
+            let required = ["colors", "typography"];
+            let missing = groups
+                .iter()
+                .filter(|(name, group)| required.contains(name) && group.is_none())
+                .map(|(name, _)| *name)
+                .chain(document.appearance.is_none().then_some("appearance"))
+                .next();
+            if let Some(field) = missing {
+                return Err(parse_error(format!(
+                    "missing field `{field}`; declare it or set `extends`"
+                )));
+            }
+        }
+
+        let mut tokens = Map::new();
+        for (name, group) in groups {
+            if let Some(group) = group {
+                tokens.insert(name.to_owned(), Value::Object(group));
+            }
+        }
+        if let Some(path) = unknown_token(&TOKEN_SCHEMA, &tokens, "") {
+            return Err(parse_error(format!("unknown token '{path}'")));
+        }
+
+        Ok(Self {
+            id: document.id,
+            extends: document.extends,
+            appearance: document.appearance,
+            tokens,
+        })
+    }
+
+    /// Reads and parses the theme file at `path`.
+    pub(crate) fn read(path: &Path) -> Result<Self, ThemeError> {
+        let format = ThemeFormat::from_path(path)
+            .ok_or_else(|| ThemeError::UnsupportedFormat(path.to_path_buf()))?;
+        let source = fs::read_to_string(path).map_err(|source| ThemeError::Io {
+            path: path.to_path_buf(),
+            source,
+        })?;
+        Self::parse(&source, format, &path.display().to_string())
+    }
+
+    /// Deep-merges the document over `base`, or over the default spacing and
+    /// elevation scales when the theme does not extend another one, then
+    /// validates every token.
+    pub(crate) fn resolve(
+        self,
+        base: Option<&ThemeDefinition>,
+    ) -> Result<(ThemeAppearance, DesignTokens), ThemeError> {
+        let mut merged = match base {
+            Some(base) => token_map(&base.tokens),
+            None => serde_json::json!({
+                "spacing": BASE_SPACING,
+                "elevations": BASE_ELEVATION,
+            }),
+        };
+        if let Value::Object(merged) = &mut merged {
+            merge_tokens(merged, self.tokens);
+        }
+        let appearance = self
+            .appearance
+            .or(base.map(|base| base.appearance))
+            .expect("checked when parsing");
+        let tokens = into_tokens(&self.id, merged)?;
+        Ok((appearance, tokens))
+    }
+}
+
+/// Returns the dotted path of the first key in `tokens` that `schema` lacks.
+fn unknown_token(
+    schema: &Map<String, Value>,
+    tokens: &Map<String, Value>,
+    prefix: &str,
+) -> Option<String> {
+    tokens.iter().find_map(|(key, value)| {
+        let path = format!("{prefix}{key}");
+        match (schema.get(key), value) {
+            (None, _) => Some(path),
+            (Some(Value::Object(schema)), Value::Object(nested)) => {
+                unknown_token(schema, nested, &format!("{path}."))
+            }
+            _ => None,
+        }
+    })
+}
+
+/// Returns the dotted path of the first key in `schema` that `tokens` lacks.
+fn missing_token(
+    schema: &Map<String, Value>,
+    tokens: &Map<String, Value>,
+    prefix: &str,
+) -> Option<String> {
+    schema.iter().find_map(|(key, expected)| {
+        let path = format!("{prefix}{key}");
+        match (expected, tokens.get(key)) {
+            (_, None) => Some(path),
+            (Value::Object(schema), Some(Value::Object(nested))) => {
+                missing_token(schema, nested, &format!("{path}."))
+            }
+            _ => None,
+        }
+    })
+}
+
+/// Recursively replaces values in `base` with those in `overrides`, keeping
+/// any nested keys the overrides leave out.
+fn merge_tokens(base: &mut Map<String, Value>, overrides: Map<String, Value>) {
+    for (key, value) in overrides {
+        match base.entry(key) {
+            Entry::Occupied(mut entry) => match (entry.get_mut(), value) {
+                (Value::Object(existing), Value::Object(nested)) => merge_tokens(existing, nested),
+                (existing, value) => *existing = value,
+            },
+            Entry::Vacant(entry) => {
+                entry.insert(value);
+            }
+        }
+    }
+}
+
+/// Validates every token of a fully merged document and converts it into
+/// [`DesignTokens`].
+fn into_tokens(id: &str, merged: Value) -> Result<DesignTokens, ThemeError> {
+    let invalid = |token: &str, reason: String| ThemeError::InvalidToken {
+        theme: id.to_owned(),
+        token: token.to_owned(),
+        reason,
+    };
+
+    let Value::Object(mut merged) = merged else {
+        unreachable!("token documents are maps");
+    };
+    if let Some(path) = missing_token(&REQUIRED_TOKENS, &merged, "") {
+        return Err(invalid(
+            &path,
+            "missing token; declare it or set `extends`".into(),
+        ));
+    }
+    let mut group = |name: &str| merged.remove(name).unwrap_or_default();
+    let colors: PaletteSource = serde_json::from_value(group("colors"))
+        .map_err(|err| invalid("colors", err.to_string()))?;
+    let typography: TypographySource = serde_json::from_value(group("typography"))
+        .map_err(|err| invalid("typography", err.to_string()))?;
+    let spacing: SpacingScale = serde_json::from_value(group("spacing"))
+        .map_err(|err| invalid("spacing", err.to_string()))?;
+    let elevations: ElevationScale = serde_json::from_value(group("elevations"))
+        .map_err(|err| invalid("elevations", err.to_string()))?;
+
+    let color = |token: &'static str, value: &str| {
+        Color::from_hex(value).map_err(|err| invalid(token, err.to_string()))
+    };
+    let core = ColorPalette {
+        primary: color("colors.primary", &colors.primary)?,
+        on_primary: color("colors.on_primary", &colors.on_primary)?,
+        accent: color("colors.accent", &colors.accent)?,
+        on_accent: color("colors.on_accent", &colors.on_accent)?,
+        background: color("colors.background", &colors.background)?,
+        surface: color("colors.surface", &colors.surface)?,
+        surface_border: color("colors.surface_border", &colors.surface_border)?,
+        muted: color("colors.muted", &colors.muted)?,
+        on_muted: color("colors.on_muted", &colors.on_muted)?,
+        success: color("colors.success", &colors.success)?,
+        warning: color("colors.warning", &colors.warning)?,
+        danger: color("colors.danger", &colors.danger)?,
+        ..light_tokens().colors
+    }
+    .with_derived_components();
+    let component = |token: &'static str, value: Option<String>, derived: Color| {
+        value.map_or(Ok(derived), |value| color(token, &value))
+    };
+    let colors = ColorPalette {
+        selection: component("colors.selection", colors.selection, core.selection)?,
+        ring: component("colors.ring", colors.ring, core.ring)?,
+        input_border: component(
+            "colors.input_border",
+            colors.input_border,
+            core.input_border,
+        )?,
+        scrollbar_thumb: component(
+            "colors.scrollbar_thumb",
+            colors.scrollbar_thumb,
+            core.scrollbar_thumb,
+        )?,
+        tab_bar: component("colors.tab_bar", colors.tab_bar, core.tab_bar)?,
+        tab_active: component("colors.tab_active", colors.tab_active, core.tab_active)?,
+        sidebar: component("colors.sidebar", colors.sidebar, core.sidebar)?,
+        on_sidebar: component("colors.on_sidebar", colors.on_sidebar, core.on_sidebar)?,
+        sidebar_border: component(
+            "colors.sidebar_border",
+            colors.sidebar_border,
+            core.sidebar_border,
+        )?,
+        chart_1: component("colors.chart_1", colors.chart_1, core.chart_1)?,
+        chart_2: component("colors.chart_2", colors.chart_2, core.chart_2)?,
+        chart_3: component("colors.chart_3", colors.chart_3, core.chart_3)?,
+        chart_4: component("colors.chart_4", colors.chart_4, core.chart_4)?,
+        chart_5: component("colors.chart_5", colors.chart_5, core.chart_5)?,
+        ..core
+    };
+
+    if typography.font_family.trim().is_empty() {
+        return Err(invalid(
+            "typography.font_family",
+            "font family must not be empty".into(),
+        ));
+    }
+    for (token, size) in [
+        ("typography.display", typography.display),
+        ("typography.headline", typography.headline),
+        ("typography.title", typography.title),
+        ("typography.body", typography.body),
+        ("typography.label", typography.label),
+    ] {
+        if !(size.is_finite() && size > 0.0) {
+            return Err(invalid(
+                token,
+                format!("font size must be positive, got {size}"),
+            ));
+        }
+    }
+    let typography = TypographyScale {
+        font_family: typography.font_family.trim().to_owned(),
+        display: typography.display,
+        headline: typography.headline,
+        title: typography.title,
+        body: typography.body,
+        label: typography.label,
+    };
+
+    for (token, value) in [
+        ("spacing.xxs", spacing.xxs),
+        ("spacing.xs", spacing.xs),
+        ("spacing.sm", spacing.sm),
+        ("spacing.md", spacing.md),
+        ("spacing.lg", spacing.lg),
+        ("spacing.xl", spacing.xl),
+        ("spacing.xxl", spacing.xxl),
+    ] {
+        if !(value.is_finite() && value >= 0.0) {
+            return Err(invalid(
+                token,
+                format!("spacing must be non-negative, got {value}"),
+            ));
+        }
+    }
+
+    for (token, level) in [
+        ("elevations.flat", elevations.flat),
+        ("elevations.raised", elevations.raised),
+        ("elevations.floating", elevations.floating),
+    ] {
+        validate_elevation(level).map_err(|reason| invalid(token, reason))?;
+    }
+
+    Ok(DesignTokens {
+        colors,
+        typography,
+        spacing,
+        elevations,
+    })
+}
+
+fn validate_elevation(level: ElevationLevel) -> Result<(), String> {
+    if !(level.blur_radius.is_finite() && level.blur_radius >= 0.0) {
+        return Err(format!(
+            "blur radius must be non-negative, got {}",
+            level.blur_radius
+        ));
+    }
+    if !(0.0..=1.0).contains(&level.opacity) {
+        return Err(format!(
+            "opacity must be between 0 and 1, got {}",
+            level.opacity
+        ));
+    }
+    if !(level.y_offset.is_finite() && level.spread_radius.is_finite()) {
+        return Err("offsets must be finite".into());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::theme::{ThemeRegistry, ThemeVariant};
+
+    const BRAND_TOML: &str = r##"
+id = "brand"
+appearance = "dark"
+
+[colors]
+primary = "#7C3AED"
+on_primary = "#ffffff"
+accent = "#f472b6"
+on_accent = "#1f0a16"
+background = "#0c0a14"
+surface = "#15111f"
+surface_border = "#2e2640"
+muted = "#1f1a2e"
+on_muted = "#d6d0e6"
+success = "#34d399"
+warning = "#fbbf24"
+danger = "#f87171"
+
+[typography]
+font_family = "IBM Plex Sans"
+display = 34.0
+headline = 26.0
+title = 19.0
+body = 15.0
+label = 12.0
+"##;
+
+    #[test]
+    fn toml_theme_converts_to_tokens() {
+        let file = ThemeFile::parse(BRAND_TOML, ThemeFormat::Toml, "brand.toml").unwrap();
+        assert_eq!(file.id, "brand");
+        let (appearance, tokens) = file.resolve(None).unwrap();
+        assert_eq!(appearance, ThemeAppearance::Dark);
+        assert_eq!(tokens.colors.primary, Color::hex("#7c3aed"));
+        assert_eq!(tokens.typography.font_family, "IBM Plex Sans");
+        assert_eq!(tokens.spacing.md, BASE_SPACING.md);
+    }
+
+    #[test]
+    fn invalid_color_reports_token_path() {
+        let source = BRAND_TOML.replace("#f472b6", "pink");
+        let file = ThemeFile::parse(&source, ThemeFormat::Toml, "brand.toml").unwrap();
+        assert!(matches!(
+            file.resolve(None),
+            Err(ThemeError::InvalidToken { token, .. }) if token == "colors.accent"
+        ));
+    }
+
+    #[test]
+    fn unknown_fields_are_rejected() {
+        let source = BRAND_TOML.replace("[typography]", "[typography]\nweight = 400");
+        assert!(matches!(
+            ThemeFile::parse(&source, ThemeFormat::Toml, "brand.toml"),
+            Err(ThemeError::Parse { origin, .. }) if origin == "brand.toml"
+        ));
+    }
+
+    #[test]
+    fn component_colors_are_derived_unless_declared() {
+        let source = BRAND_TOML.replace("[typography]", "sidebar = \"#100c1a\"\n\n[typography]");
+        let file = ThemeFile::parse(&source, ThemeFormat::Toml, "brand.toml").unwrap();
+        let (_, tokens) = file.resolve(None).unwrap();
+        assert_eq!(tokens.colors.sidebar, Color::hex("#100c1a"));
+        assert_eq!(tokens.colors.ring, tokens.colors.primary);
+        assert_eq!(tokens.colors.chart_5, tokens.colors.danger);
+    }
+
+    #[test]
+    fn extending_theme_overrides_only_listed_tokens() {
+        let source = r##"
+id = "brand-dark"
+extends = "dark"
+
+[colors]
+primary = "#7c3aed"
+
+[elevations.raised]
+opacity = 0.5
+"##;
+        let file = ThemeFile::parse(source, ThemeFormat::Toml, "brand-dark.toml").unwrap();
+        assert_eq!(file.extends.as_deref(), Some("dark"));
+
+        let registry = ThemeRegistry::new();
+        let base = registry.definition(ThemeVariant::Dark);
+        let (appearance, tokens) = file.resolve(Some(&base)).unwrap();
+        assert_eq!(appearance, ThemeAppearance::Dark);
+        assert_eq!(tokens.colors.primary, Color::hex("#7c3aed"));
+        assert_eq!(tokens.colors.accent, base.tokens.colors.accent);
+        assert_eq!(tokens.typography, base.tokens.typography);
+        assert_eq!(
+            tokens.elevations.raised,
+            ElevationLevel {
+                opacity: 0.5,
+                ..base.tokens.elevations.raised
+            }
+        );
+    }
+
+    #[test]
+    fn unknown_nested_tokens_name_their_path() {
+        let source = "id = \"brand\"\nextends = \"dark\"\n[colors]\nprimery = \"#7c3aed\"\n";
+        assert!(matches!(
+            ThemeFile::parse(source, ThemeFormat::Toml, "brand.toml"),
+            Err(ThemeError::Parse { message, .. }) if message == "unknown token 'colors.primery'"
+        ));
+    }
+
+    #[test]
+    fn standalone_themes_must_be_complete() {
+        let source = BRAND_TOML.replace("on_accent = \"#1f0a16\"\n", "");
+        let file = ThemeFile::parse(&source, ThemeFormat::Toml, "brand.toml").unwrap();
+        assert!(matches!(
+            file.resolve(None),
+            Err(ThemeError::InvalidToken { token, .. }) if token == "colors.on_accent"
+        ));
+
+        let source = BRAND_TOML.replace("appearance = \"dark\"\n", "");
+        assert!(matches!(
+            ThemeFile::parse(&source, ThemeFormat::Toml, "brand.toml"),
+            Err(ThemeError::Parse { .. })
+        ));
+    }
+}
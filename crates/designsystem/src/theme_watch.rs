@@ -0,0 +1,174 @@
+//! File watcher that hot-reloads custom themes while the application runs.
+
+use std::{
+    collections::BTreeSet,
+    path::{Path, PathBuf},
+    sync::mpsc::{channel, TryRecvError},
+    time::Duration,
+};
+
+use gpui::{App, Task};
+use notify::{EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+
+use crate::{
+    theme::{ThemeError, ThemeRegistry, ThemeVariant},
+    theme_file::ThemeFormat,
+};
+
+/// Environment variable naming a theme directory that
+/// [`ThemeRegistry::load_and_watch_env`] loads and watches.
+pub const THEME_DIR_ENV: &str = "GPUI_THEME_DIR";
+
+/// How often queued file system events are drained on the foreground thread.
+/// Editors usually emit several writes per save, so batching them avoids
+/// re-applying the same theme repeatedly.
+const POLL_INTERVAL: Duration = Duration::from_millis(200);
+
+/// Handle that keeps a theme directory watch alive.
+///
+/// Dropping the handle stops watching; call [`ThemeWatcher::detach`] to keep
+/// watching for the rest of the process.
+#[must_use = "the theme directory is only watched while the handle is alive"]
+pub struct ThemeWatcher {
+    watcher: RecommendedWatcher,
+    task: Task<()>,
+}
+
+impl ThemeWatcher {
+    /// Keeps the watcher running until the application exits.
+    pub fn detach(self) {
+        self.task.detach();
+        std::mem::forget(self.watcher);
+    }
+}
+
+impl ThemeRegistry {
+    /// Reloads a single theme file, resolves the file-backed themes that
+    /// extend it again, and re-applies the active theme when it is among
+    /// them.
+    ///
+    /// When the file fails to parse or validate, the previously registered
+    /// definition is kept, so the last good version stays on screen.
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`ThemeRegistry::load_file`], for the file
+    /// itself or for the first dependent theme that no longer resolves.
+    pub fn reload_file(
+        &self,
+        path: impl AsRef<Path>,
+        cx: &mut App,
+    ) -> Result<ThemeVariant, ThemeError> {
+        let variant = self.load_file(path)?;
+        let dependents = self.dependents(variant);
+        let resolved = self.re_resolve(&dependents);
+        let active = self.active();
+        if active == variant || dependents.contains(&active) {
+            self.apply(active, cx);
+        }
+        resolved.map(|()| variant)
+    }
+
+    /// Watches `dir` for theme file changes and reloads them as they are
+    /// saved, refreshing every window afterwards.
+    ///
+    /// Reload failures are passed to `on_error` instead of interrupting the
+    /// watch. Load the directory once with [`ThemeRegistry::load_dir`] before
+    /// watching it so existing files are registered up front.
+    ///
+    /// Deleting a theme file does not unregister its theme: other themes may
+    /// extend it and windows may be showing it, so it stays available until
+    /// the application restarts.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ThemeError::Watch`] when the platform watcher cannot be
+    /// created or the directory cannot be watched.
+    pub fn watch_dir(
+        &self,
+        dir: impl Into<PathBuf>,
+        cx: &mut App,
+        on_error: impl Fn(&ThemeError, &mut App) + 'static,
+    ) -> Result<ThemeWatcher, ThemeError> {
+        let dir = dir.into();
+        let (tx, rx) = channel();
+        let mut watcher = notify::recommended_watcher(move |event| {
+            let _ = tx.send(event);
+        })?;
+        watcher.watch(&dir, RecursiveMode::NonRecursive)?;
+
+        let registry = self.clone();
+        let task = cx.spawn(async move |cx| loop {
+            cx.background_executor().timer(POLL_INTERVAL).await;
+
+            let mut changed = BTreeSet::new();
+            let mut errors = Vec::new();
+            loop {
+                match rx.try_recv() {
+                    Ok(Ok(event)) => {
+                        // Removals are ignored; see the method docs.
+                        if matches!(event.kind, EventKind::Create(_) | EventKind::Modify(_)) {
+                            changed.extend(
+                                event
+                                    .paths
+                                    .into_iter()
+                                    .filter(|path| ThemeFormat::from_path(path).is_some()),
+                            );
+                        }
+                    }
+                    Ok(Err(err)) => errors.push(ThemeError::Watch(err)),
+                    Err(TryRecvError::Empty) => break,
+                    Err(TryRecvError::Disconnected) => return,
+                }
+            }
+            if changed.is_empty() && errors.is_empty() {
+                continue;
+            }
+
+            let updated = cx.update(|cx| {
+                for err in &errors {
+                    on_error(err, cx);
+                }
+                for path in &changed {
+                    // Editors may briefly leave a file missing while saving
+                    // atomically; the follow-up create event reloads it.
+                    if !path.exists() {
+                        continue;
+                    }
+                    if let Err(err) = registry.reload_file(path, cx) {
+                        on_error(&err, cx);
+                    }
+                }
+                cx.refresh_windows();
+            });
+            if updated.is_err() {
+                return;
+            }
+        });
+
+        Ok(ThemeWatcher { watcher, task })
+    }
+
+    /// Loads and watches the directory named by [`THEME_DIR_ENV`], as the
+    /// workbench and gallery do at startup.
+    ///
+    /// Returns `Ok(None)` when the variable is not set. Reload failures are
+    /// passed to `on_error`, as with [`ThemeRegistry::watch_dir`].
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors of [`ThemeRegistry::load_dir`] and
+    /// [`ThemeRegistry::watch_dir`]; the directory is not watched when it
+    /// fails to load.
+    pub fn load_and_watch_env(
+        &self,
+        cx: &mut App,
+        on_error: impl Fn(&ThemeError, &mut App) + 'static,
+    ) -> Result<Option<ThemeWatcher>, ThemeError> {
+        let Some(dir) = std::env::var_os(THEME_DIR_ENV) else {
+            return Ok(None);
+        };
+        self.load_dir(&dir)?;
+        self.watch_dir(dir, cx, on_error).map(Some)
+    }
+}
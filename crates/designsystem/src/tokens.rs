@@ -1,38 +1,185 @@
 //! Core design tokens used to build Workspace themes.
 
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::{
+    color::{Color, Oklch},
+    contrast::{correct_contrast, ContrastCorrection},
+};
 
 /// Shared color palette for a theme variant.
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ColorPalette {
     /// Brand primary surface color.
-    pub primary: &'static str,
+    pub primary: Color,
     /// Foreground color rendered atop [`primary`].
-    pub on_primary: &'static str,
+    pub on_primary: Color,
     /// Accent surface color used for callouts.
-    pub accent: &'static str,
+    pub accent: Color,
     /// Foreground color rendered atop [`accent`].
-    pub on_accent: &'static str,
+    pub on_accent: Color,
     /// Application background color.
-    pub background: &'static str,
+    pub background: Color,
     /// Default surface color for panels and cards.
-    pub surface: &'static str,
+    pub surface: Color,
     /// Border color separating surfaces.
-    pub surface_border: &'static str,
+    pub surface_border: Color,
     /// Muted fill color for secondary controls.
-    pub muted: &'static str,
+    pub muted: Color,
     /// Foreground color for muted surfaces.
-    pub on_muted: &'static str,
+    pub on_muted: Color,
     /// Color used to communicate success states.
-    pub success: &'static str,
+    pub success: Color,
     /// Color used to communicate warning states.
-    pub warning: &'static str,
+    pub warning: Color,
     /// Color used to communicate error states.
-    pub danger: &'static str,
+    pub danger: Color,
+    /// Highlight behind selected text.
+    pub selection: Color,
+    /// Focus ring drawn around the focused control.
+    pub ring: Color,
+    /// Border of text inputs and other form fields.
+    pub input_border: Color,
+    /// Draggable thumb of scrollbars.
+    pub scrollbar_thumb: Color,
+    /// Background of tab strips.
+    pub tab_bar: Color,
+    /// Background of the selected tab.
+    pub tab_active: Color,
+    /// Background of navigation sidebars.
+    pub sidebar: Color,
+    /// Foreground color rendered atop [`sidebar`].
+    pub on_sidebar: Color,
+    /// Border separating a sidebar from the content next to it.
+    pub sidebar_border: Color,
+    /// First chart series color.
+    pub chart_1: Color,
+    /// Second chart series color.
+    pub chart_2: Color,
+    /// Third chart series color.
+    pub chart_3: Color,
+    /// Fourth chart series color.
+    pub chart_4: Color,
+    /// Fifth chart series color.
+    pub chart_5: Color,
+}
+
+impl ColorPalette {
+    /// Component tokens that theme files and token imports may omit; missing
+    /// ones are filled in by [`ColorPalette::with_derived_components`].
+    pub const COMPONENT_TOKENS: [&'static str; 14] = [
+        "selection",
+        "ring",
+        "input_border",
+        "scrollbar_thumb",
+        "tab_bar",
+        "tab_active",
+        "sidebar",
+        "on_sidebar",
+        "sidebar_border",
+        "chart_1",
+        "chart_2",
+        "chart_3",
+        "chart_4",
+        "chart_5",
+    ];
+
+    /// Returns every color as `(token name, color)` pairs in declaration
+    /// order.
+    #[must_use]
+    pub const fn entries(&self) -> [(&'static str, Color); 26] {
+        [
+            ("primary", self.primary),
+            ("on_primary", self.on_primary),
+            ("accent", self.accent),
+            ("on_accent", self.on_accent),
+            ("background", self.background),
+            ("surface", self.surface),
+            ("surface_border", self.surface_border),
+            ("muted", self.muted),
+            ("on_muted", self.on_muted),
+            ("success", self.success),
+            ("warning", self.warning),
+            ("danger", self.danger),
+            ("selection", self.selection),
+            ("ring", self.ring),
+            ("input_border", self.input_border),
+            ("scrollbar_thumb", self.scrollbar_thumb),
+            ("tab_bar", self.tab_bar),
+            ("tab_active", self.tab_active),
+            ("sidebar", self.sidebar),
+            ("on_sidebar", self.on_sidebar),
+            ("sidebar_border", self.sidebar_border),
+            ("chart_1", self.chart_1),
+            ("chart_2", self.chart_2),
+            ("chart_3", self.chart_3),
+            ("chart_4", self.chart_4),
+            ("chart_5", self.chart_5),
+        ]
+    }
+
+    /// Recomputes the component colors from the core roles: selection and
+    /// focus ring from `primary`, inputs and borders from `surface_border`,
+    /// tabs and the sidebar from the surfaces, and chart series from the
+    /// brand and status colors.
+    #[must_use]
+    pub fn with_derived_components(self) -> Self {
+        Self {
+            selection: self.primary.with_alpha(0x40),
+            ring: self.primary,
+            input_border: self.surface_border,
+            scrollbar_thumb: self.on_muted.with_alpha(0x80),
+            tab_bar: self.background,
+            tab_active: self.surface,
+            sidebar: self.surface,
+            on_sidebar: self.on_muted,
+            sidebar_border: self.surface_border,
+            chart_1: self.primary,
+            chart_2: self.accent,
+            chart_3: self.success,
+            chart_4: self.warning,
+            chart_5: self.danger,
+            ..self
+        }
+    }
+
+    /// Blends every color towards `other`; see [`Color::mix`].
+    #[must_use]
+    pub fn mix(&self, other: &Self, amount: f32) -> Self {
+        Self {
+            primary: self.primary.mix(other.primary, amount),
+            on_primary: self.on_primary.mix(other.on_primary, amount),
+            accent: self.accent.mix(other.accent, amount),
+            on_accent: self.on_accent.mix(other.on_accent, amount),
+            background: self.background.mix(other.background, amount),
+            surface: self.surface.mix(other.surface, amount),
+            surface_border: self.surface_border.mix(other.surface_border, amount),
+            muted: self.muted.mix(other.muted, amount),
+            on_muted: self.on_muted.mix(other.on_muted, amount),
+            success: self.success.mix(other.success, amount),
+            warning: self.warning.mix(other.warning, amount),
+            danger: self.danger.mix(other.danger, amount),
+            selection: self.selection.mix(other.selection, amount),
+            ring: self.ring.mix(other.ring, amount),
+            input_border: self.input_border.mix(other.input_border, amount),
+            scrollbar_thumb: self.scrollbar_thumb.mix(other.scrollbar_thumb, amount),
+            tab_bar: self.tab_bar.mix(other.tab_bar, amount),
+            tab_active: self.tab_active.mix(other.tab_active, amount),
+            sidebar: self.sidebar.mix(other.sidebar, amount),
+            on_sidebar: self.on_sidebar.mix(other.on_sidebar, amount),
+            sidebar_border: self.sidebar_border.mix(other.sidebar_border, amount),
+            chart_1: self.chart_1.mix(other.chart_1, amount),
+            chart_2: self.chart_2.mix(other.chart_2, amount),
+            chart_3: self.chart_3.mix(other.chart_3, amount),
+            chart_4: self.chart_4.mix(other.chart_4, amount),
+            chart_5: self.chart_5.mix(other.chart_5, amount),
+        }
+    }
 }
 
 /// Canonical spacing scale that maps semantic names to pixel values.
-#[derive(Debug, Clone, Copy, Serialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub struct SpacingScale {
     /// Extra-extra-small spacing value.
     pub xxs: f32,
@@ -64,6 +211,21 @@ impl SpacingScale {
             SpacingToken::XXL => self.xxl,
         }
     }
+
+    /// Returns every spacing value as `(token name, pixels)` pairs from
+    /// smallest to largest.
+    #[must_use]
+    pub const fn entries(&self) -> [(&'static str, f32); 7] {
+        [
+            ("xxs", self.xxs),
+            ("xs", self.xs),
+            ("sm", self.sm),
+            ("md", self.md),
+            ("lg", self.lg),
+            ("xl", self.xl),
+            ("xxl", self.xxl),
+        ]
+    }
 }
 
 /// Named spacing tokens that are used throughout layout helpers.
@@ -86,10 +248,10 @@ pub enum SpacingToken {
 }
 
 /// Typographic scale storing base sizes for text styles.
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct TypographyScale {
     /// Default font family for text rendering.
-    pub font_family: &'static str,
+    pub font_family: String,
     /// Display heading size in pixels.
     pub display: f32,
     /// Headline size in pixels.
@@ -102,8 +264,51 @@ pub struct TypographyScale {
     pub label: f32,
 }
 
-/// Declarative elevation tokens that can be transformed into GPUI shadows.
-#[derive(Debug, Clone, Copy, Serialize)]
+impl TypographyScale {
+    /// Returns every text size as `(style name, pixels)` pairs from largest
+    /// to smallest.
+    #[must_use]
+    pub const fn sizes(&self) -> [(&'static str, f32); 5] {
+        [
+            ("display", self.display),
+            ("headline", self.headline),
+            ("title", self.title),
+            ("body", self.body),
+            ("label", self.label),
+        ]
+    }
+
+    /// Returns the text size for the role in pixels.
+    #[must_use]
+    pub const fn size(&self, role: TextRole) -> f32 {
+        match role {
+            TextRole::Display => self.display,
+            TextRole::Headline => self.headline,
+            TextRole::Title => self.title,
+            TextRole::Body => self.body,
+            TextRole::Label => self.label,
+        }
+    }
+}
+
+/// Semantic text roles resolved against the [`TypographyScale`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum TextRole {
+    /// Hero numbers and splash headings.
+    Display,
+    /// Page headings.
+    Headline,
+    /// Card and section titles.
+    Title,
+    /// Running text.
+    Body,
+    /// Captions, metadata, and form labels.
+    Label,
+}
+
+/// Declarative elevation tokens, converted into GPUI shadows by
+/// [`ElevationLevel::box_shadows`].
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub struct ElevationLevel {
     /// Vertical offset in pixels for the shadow.
     pub y_offset: f32,
@@ -116,7 +321,7 @@ pub struct ElevationLevel {
 }
 
 /// Collection of elevation definitions for common surfaces.
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ElevationScale {
     /// Flat surfaces without any elevation.
     pub flat: ElevationLevel,
@@ -126,8 +331,43 @@ pub struct ElevationScale {
     pub floating: ElevationLevel,
 }
 
+impl ElevationScale {
+    /// Returns every elevation as `(token name, level)` pairs from flat to
+    /// floating.
+    #[must_use]
+    pub const fn entries(&self) -> [(&'static str, ElevationLevel); 3] {
+        [
+            ("flat", self.flat),
+            ("raised", self.raised),
+            ("floating", self.floating),
+        ]
+    }
+
+    /// Returns the level assigned to the elevation token.
+    #[must_use]
+    pub const fn level(&self, elevation: ElevationToken) -> ElevationLevel {
+        match elevation {
+            ElevationToken::Flat => self.flat,
+            ElevationToken::Raised => self.raised,
+            ElevationToken::Floating => self.floating,
+        }
+    }
+}
+
+/// Named elevation tokens requested by surfaces.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub enum ElevationToken {
+    /// Surfaces that sit on the page without a shadow.
+    Flat,
+    /// Cards and panels lifted slightly above the page.
+    #[default]
+    Raised,
+    /// Dialogs, popovers and other overlays.
+    Floating,
+}
+
 /// Bundles the complete token set for a theme variant.
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct DesignTokens {
     /// Color palette applied to the variant.
     pub colors: ColorPalette,
@@ -139,7 +379,7 @@ pub struct DesignTokens {
     pub elevations: ElevationScale,
 }
 
-const BASE_SPACING: SpacingScale = SpacingScale {
+pub(crate) const BASE_SPACING: SpacingScale = SpacingScale {
     xxs: 2.0,
     xs: 4.0,
     sm: 8.0,
@@ -149,7 +389,7 @@ const BASE_SPACING: SpacingScale = SpacingScale {
     xxl: 32.0,
 };
 
-const BASE_ELEVATION: ElevationScale = ElevationScale {
+pub(crate) const BASE_ELEVATION: ElevationScale = ElevationScale {
     flat: ElevationLevel {
         y_offset: 0.0,
         blur_radius: 0.0,
@@ -174,21 +414,35 @@ const BASE_ELEVATION: ElevationScale = ElevationScale {
 pub fn light_tokens() -> DesignTokens {
     DesignTokens {
         colors: ColorPalette {
-            primary: "#2563eb",
-            on_primary: "#f8fafc",
-            accent: "#0ea5e9",
-            on_accent: "#ecfeff",
-            background: "#f5f7fa",
-            surface: "#ffffff",
-            surface_border: "#cbd5f5",
-            muted: "#e2e8f0",
-            on_muted: "#475569",
-            success: "#10b981",
-            warning: "#f59e0b",
-            danger: "#ef4444",
+            primary: Color::hex("#2563eb"),
+            on_primary: Color::hex("#f8fafc"),
+            accent: Color::hex("#0ea5e9"),
+            on_accent: Color::hex("#ecfeff"),
+            background: Color::hex("#f5f7fa"),
+            surface: Color::hex("#ffffff"),
+            surface_border: Color::hex("#cbd5f5"),
+            muted: Color::hex("#e2e8f0"),
+            on_muted: Color::hex("#475569"),
+            success: Color::hex("#10b981"),
+            warning: Color::hex("#f59e0b"),
+            danger: Color::hex("#ef4444"),
+            selection: Color::hex("#2563eb33"),
+            ring: Color::hex("#3b82f6"),
+            input_border: Color::hex("#cbd5e1"),
+            scrollbar_thumb: Color::hex("#94a3b880"),
+            tab_bar: Color::hex("#f1f5f9"),
+            tab_active: Color::hex("#ffffff"),
+            sidebar: Color::hex("#f8fafc"),
+            on_sidebar: Color::hex("#334155"),
+            sidebar_border: Color::hex("#e2e8f0"),
+            chart_1: Color::hex("#2563eb"),
+            chart_2: Color::hex("#0ea5e9"),
+            chart_3: Color::hex("#10b981"),
+            chart_4: Color::hex("#f59e0b"),
+            chart_5: Color::hex("#8b5cf6"),
         },
         typography: TypographyScale {
-            font_family: "Inter",
+            font_family: "Inter".into(),
             display: 36.0,
             headline: 28.0,
             title: 20.0,
@@ -204,21 +458,35 @@ pub fn light_tokens() -> DesignTokens {
 pub fn dark_tokens() -> DesignTokens {
     DesignTokens {
         colors: ColorPalette {
-            primary: "#60a5fa",
-            on_primary: "#0b1120",
-            accent: "#38bdf8",
-            on_accent: "#082f49",
-            background: "#0f172a",
-            surface: "#111c33",
-            surface_border: "#1f2a44",
-            muted: "#1e293b",
-            on_muted: "#cbd5f5",
-            success: "#34d399",
-            warning: "#fbbf24",
-            danger: "#f87171",
+            primary: Color::hex("#60a5fa"),
+            on_primary: Color::hex("#0b1120"),
+            accent: Color::hex("#38bdf8"),
+            on_accent: Color::hex("#082f49"),
+            background: Color::hex("#0f172a"),
+            surface: Color::hex("#111c33"),
+            surface_border: Color::hex("#1f2a44"),
+            muted: Color::hex("#1e293b"),
+            on_muted: Color::hex("#cbd5f5"),
+            success: Color::hex("#34d399"),
+            warning: Color::hex("#fbbf24"),
+            danger: Color::hex("#f87171"),
+            selection: Color::hex("#60a5fa40"),
+            ring: Color::hex("#93c5fd"),
+            input_border: Color::hex("#334155"),
+            scrollbar_thumb: Color::hex("#47556980"),
+            tab_bar: Color::hex("#0b1324"),
+            tab_active: Color::hex("#111c33"),
+            sidebar: Color::hex("#0c1528"),
+            on_sidebar: Color::hex("#cbd5f5"),
+            sidebar_border: Color::hex("#1f2a44"),
+            chart_1: Color::hex("#60a5fa"),
+            chart_2: Color::hex("#38bdf8"),
+            chart_3: Color::hex("#34d399"),
+            chart_4: Color::hex("#fbbf24"),
+            chart_5: Color::hex("#a78bfa"),
         },
         typography: TypographyScale {
-            font_family: "Inter",
+            font_family: "Inter".into(),
             display: 36.0,
             headline: 28.0,
             title: 20.0,
@@ -234,21 +502,35 @@ pub fn dark_tokens() -> DesignTokens {
 pub fn high_contrast_tokens() -> DesignTokens {
     DesignTokens {
         colors: ColorPalette {
-            primary: "#1d4ed8",
-            on_primary: "#ffffff",
-            accent: "#0ea5e9",
-            on_accent: "#000000",
-            background: "#020617",
-            surface: "#050b1e",
-            surface_border: "#f8fafc",
-            muted: "#111827",
-            on_muted: "#e2e8f0",
-            success: "#22c55e",
-            warning: "#facc15",
-            danger: "#f87171",
+            primary: Color::hex("#1d4ed8"),
+            on_primary: Color::hex("#ffffff"),
+            accent: Color::hex("#0ea5e9"),
+            on_accent: Color::hex("#000000"),
+            background: Color::hex("#020617"),
+            surface: Color::hex("#050b1e"),
+            surface_border: Color::hex("#f8fafc"),
+            muted: Color::hex("#111827"),
+            on_muted: Color::hex("#e2e8f0"),
+            success: Color::hex("#22c55e"),
+            warning: Color::hex("#facc15"),
+            danger: Color::hex("#f87171"),
+            selection: Color::hex("#1d4ed8"),
+            ring: Color::hex("#facc15"),
+            input_border: Color::hex("#f8fafc"),
+            scrollbar_thumb: Color::hex("#e2e8f0"),
+            tab_bar: Color::hex("#020617"),
+            tab_active: Color::hex("#111827"),
+            sidebar: Color::hex("#020617"),
+            on_sidebar: Color::hex("#ffffff"),
+            sidebar_border: Color::hex("#f8fafc"),
+            chart_1: Color::hex("#38bdf8"),
+            chart_2: Color::hex("#facc15"),
+            chart_3: Color::hex("#22c55e"),
+            chart_4: Color::hex("#f472b6"),
+            chart_5: Color::hex("#a78bfa"),
         },
         typography: TypographyScale {
-            font_family: "Inter",
+            font_family: "Inter".into(),
             display: 40.0,
             headline: 30.0,
             title: 22.0,
@@ -270,9 +552,244 @@ pub fn high_contrast_tokens() -> DesignTokens {
     }
 }
 
+/// Contrast modes a [`PaletteSeed`] can generate palettes for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum PaletteMode {
+    /// Light surfaces with dark text, matching [`light_tokens`].
+    Light,
+    /// Dark surfaces with light text, matching [`dark_tokens`].
+    Dark,
+    /// Maximum-contrast surfaces, matching [`high_contrast_tokens`].
+    HighContrast,
+}
+
+/// Error returned when a palette seed cannot be parsed.
+#[derive(Debug, Error)]
+pub enum PaletteError {
+    /// Raised when a seed color is not a `#rrggbb` hex string.
+    #[error("seed color '{0}' is not a '#rrggbb' hex string")]
+    InvalidSeed(String),
+}
+
+/// Brand colors that complete palettes are derived from.
+///
+/// Each palette role is taken from a tonal ramp of the seed hue, so a single
+/// brand color yields matching light, dark, and high-contrast themes.
+///
+/// ```
+/// use designsystem::{PaletteMode, PaletteSeed};
+///
+/// let seed = PaletteSeed::new("#7c3aed")?.with_accent("#f472b6")?;
+/// let tokens = seed.tokens(PaletteMode::Dark);
+/// assert!(tokens.colors.primary.is_opaque());
+/// # Ok::<(), designsystem::PaletteError>(())
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct PaletteSeed {
+    primary: Oklch,
+    accent: Oklch,
+}
+
+/// Hue rotation used to derive an accent when none is supplied.
+const ACCENT_HUE_SHIFT: f32 = 40.0;
+/// Chroma ceiling for backgrounds, surfaces, and borders, which are tinted
+/// toward the brand hue without becoming colorful.
+const NEUTRAL_CHROMA: f32 = 0.012;
+/// Chroma ceiling for muted foreground text.
+const MUTED_TEXT_CHROMA: f32 = 0.03;
+/// Chroma used for the status colors.
+const STATUS_CHROMA: f32 = 0.17;
+/// OKLCH hues for success, warning, and danger.
+const STATUS_HUES: [f32; 3] = [155.0, 80.0, 27.0];
+
+/// OKLCH lightness for every palette role in one [`PaletteMode`].
+struct ToneMap {
+    primary: f32,
+    on_primary: f32,
+    accent: f32,
+    on_accent: f32,
+    background: f32,
+    surface: f32,
+    surface_border: f32,
+    muted: f32,
+    on_muted: f32,
+    status: [f32; 3],
+    min_ratio: f32,
+}
+
+const LIGHT_TONES: ToneMap = ToneMap {
+    primary: 0.52,
+    on_primary: 0.985,
+    accent: 0.5,
+    on_accent: 0.99,
+    background: 0.975,
+    surface: 1.0,
+    surface_border: 0.88,
+    muted: 0.93,
+    on_muted: 0.42,
+    status: [0.55, 0.6, 0.55],
+    min_ratio: 4.5,
+};
+
+const DARK_TONES: ToneMap = ToneMap {
+    primary: 0.74,
+    on_primary: 0.18,
+    accent: 0.78,
+    on_accent: 0.2,
+    background: 0.18,
+    surface: 0.21,
+    surface_border: 0.3,
+    muted: 0.27,
+    on_muted: 0.88,
+    status: [0.8, 0.85, 0.72],
+    min_ratio: 4.5,
+};
+
+const HIGH_CONTRAST_TONES: ToneMap = ToneMap {
+    primary: 0.45,
+    on_primary: 1.0,
+    accent: 0.78,
+    on_accent: 0.0,
+    background: 0.13,
+    surface: 0.16,
+    surface_border: 0.97,
+    muted: 0.23,
+    on_muted: 0.93,
+    status: [0.78, 0.88, 0.74],
+    min_ratio: 7.0,
+};
+
+impl PaletteSeed {
+    /// Creates a seed from a primary brand color. The accent is derived by
+    /// rotating the primary hue.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PaletteError::InvalidSeed`] when `primary` is not a
+    /// `#rrggbb` hex string. Seeds are opaque, so `#rrggbbaa` is rejected
+    /// too.
+    pub fn new(primary: &str) -> Result<Self, PaletteError> {
+        let primary = parse_seed(primary)?;
+        let accent = Oklch {
+            h: (primary.h + ACCENT_HUE_SHIFT).rem_euclid(360.0),
+            ..primary
+        };
+        Ok(Self { primary, accent })
+    }
+
+    /// Uses a second brand color for the accent role.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PaletteError::InvalidSeed`] when `accent` is not a `#rrggbb`
+    /// hex string.
+    pub fn with_accent(self, accent: &str) -> Result<Self, PaletteError> {
+        Ok(Self {
+            accent: parse_seed(accent)?,
+            ..self
+        })
+    }
+
+    /// Generates a complete color palette for `mode`.
+    ///
+    /// Shorthand for the palette of [`PaletteSeed::correction`]; use that
+    /// method to find out whether every `on_*` pairing met its target.
+    #[must_use]
+    pub fn palette(&self, mode: PaletteMode) -> ColorPalette {
+        self.correction(mode).palette
+    }
+
+    /// Generates the palette for `mode` along with the contrast fixes
+    /// applied to it.
+    ///
+    /// Foreground colors are corrected with
+    /// [`correct_contrast`](crate::correct_contrast) toward 4.5:1, or 7:1 in
+    /// [`PaletteMode::HighContrast`]. A pairing that no lightness of the seed
+    /// hue can bring to the target is left at its best ratio and listed by
+    /// [`ContrastCorrection::unresolved`].
+    #[must_use]
+    #[allow(clippy::missing_panics_doc)]
+    pub fn correction(&self, mode: PaletteMode) -> ContrastCorrection {
+        let tones = match mode {
+            PaletteMode::Light => &LIGHT_TONES,
+            PaletteMode::Dark => &DARK_TONES,
+            PaletteMode::HighContrast => &HIGH_CONTRAST_TONES,
+        };
+        let primary = self.primary;
+        let accent = self.accent;
+        let [success, warning, danger] = std::array::from_fn(|index| {
+            tone(
+                Oklch {
+                    l: 0.0,
+                    c: STATUS_CHROMA,
+                    h: STATUS_HUES[index],
+                },
+                tones.status[index],
+                STATUS_CHROMA,
+            )
+        });
+
+        let palette = ColorPalette {
+            primary: tone(primary, tones.primary, primary.c),
+            on_primary: tone(primary, tones.on_primary, NEUTRAL_CHROMA),
+            accent: tone(accent, tones.accent, accent.c),
+            on_accent: tone(accent, tones.on_accent, NEUTRAL_CHROMA),
+            background: tone(primary, tones.background, NEUTRAL_CHROMA),
+            surface: tone(primary, tones.surface, NEUTRAL_CHROMA),
+            surface_border: tone(primary, tones.surface_border, NEUTRAL_CHROMA),
+            muted: tone(primary, tones.muted, NEUTRAL_CHROMA),
+            on_muted: tone(primary, tones.on_muted, MUTED_TEXT_CHROMA),
+            success,
+            warning,
+            danger,
+            // Every component color is recomputed from the roles above.
+            ..light_tokens().colors
+        }
+        .with_derived_components();
+        correct_contrast(&palette, tones.min_ratio).expect("constant target ratio is in range")
+    }
+
+    /// Generates a full token set for `mode`, pairing the generated palette
+    /// with the typography, spacing, and elevation of the matching built-in
+    /// theme.
+    #[must_use]
+    pub fn tokens(&self, mode: PaletteMode) -> DesignTokens {
+        let base = match mode {
+            PaletteMode::Light => light_tokens(),
+            PaletteMode::Dark => dark_tokens(),
+            PaletteMode::HighContrast => high_contrast_tokens(),
+        };
+        DesignTokens {
+            colors: self.palette(mode),
+            ..base
+        }
+    }
+}
+
+/// Parses an opaque `#rrggbb` seed. `#rrggbbaa` is rejected rather than
+/// having its alpha silently dropped by the OKLCH conversion.
+fn parse_seed(hex: &str) -> Result<Oklch, PaletteError> {
+    Color::from_hex(hex)
+        .ok()
+        .filter(|_| hex.len() == 7)
+        .map(Oklch::from_color)
+        .ok_or_else(|| PaletteError::InvalidSeed(hex.to_owned()))
+}
+
+/// Picks a tone from the ramp of `color`'s hue at lightness `l`.
+fn tone(color: Oklch, l: f32, max_chroma: f32) -> Color {
+    Oklch {
+        l,
+        c: color.c.min(max_chroma),
+        h: color.h,
+    }
+    .to_color()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::contrast::{audit_contrast, contrast_ratio};
 
     #[test]
     fn spacing_returns_expected_px() {
@@ -280,6 +797,15 @@ mod tests {
         assert_eq!(spacing.as_px(SpacingToken::MD), 12.0);
     }
 
+    #[test]
+    fn text_roles_follow_the_theme_scale() {
+        assert_eq!(light_tokens().typography.size(TextRole::Title), 20.0);
+        assert_eq!(
+            high_contrast_tokens().typography.size(TextRole::Title),
+            22.0
+        );
+    }
+
     #[test]
     fn theme_contrast_ratios() {
         let themes = [light_tokens(), dark_tokens(), high_contrast_tokens()];
@@ -305,41 +831,58 @@ mod tests {
         }
     }
 
-    fn contrast_ratio(foreground: &str, background: &str) -> f32 {
-        let fg = linear_rgb(foreground);
-        let bg = linear_rgb(background);
-        let l1 = relative_luminance(fg);
-        let l2 = relative_luminance(bg);
-        let (lighter, darker) = if l1 > l2 { (l1, l2) } else { (l2, l1) };
-        (lighter + 0.05) / (darker + 0.05)
-    }
-
-    fn linear_rgb(hex: &str) -> (f32, f32, f32) {
-        let bytes = hex_to_bytes(hex);
-        (
-            srgb_to_linear(bytes[0]),
-            srgb_to_linear(bytes[1]),
-            srgb_to_linear(bytes[2]),
-        )
-    }
-
-    fn hex_to_bytes(hex: &str) -> [f32; 3] {
-        let hex = hex.trim_start_matches('#');
-        let r = u8::from_str_radix(&hex[0..2], 16).expect("red");
-        let g = u8::from_str_radix(&hex[2..4], 16).expect("green");
-        let b = u8::from_str_radix(&hex[4..6], 16).expect("blue");
-        [r as f32 / 255.0, g as f32 / 255.0, b as f32 / 255.0]
-    }
+    #[test]
+    fn seed_generates_accessible_palettes() {
+        // A saturated brand color plus near-white, near-black, and grey seeds,
+        // whose tonal ramps have the least room to move.
+        for hex in ["#7c3aed", "#fefefe", "#050505", "#808080"] {
+            let seed = PaletteSeed::new(hex).unwrap();
+            for (mode, threshold) in [
+                (PaletteMode::Light, 4.5),
+                (PaletteMode::Dark, 4.5),
+                (PaletteMode::HighContrast, 7.0),
+            ] {
+                let correction = seed.correction(mode);
+                assert_eq!(correction.unresolved().count(), 0, "{hex} {mode:?}");
 
-    fn srgb_to_linear(value: f32) -> f32 {
-        if value <= 0.04045 {
-            value / 12.92
-        } else {
-            ((value + 0.055) / 1.055).powf(2.4)
+                let tokens = seed.tokens(mode);
+                let report = audit_contrast(&tokens);
+                for check in report
+                    .checks
+                    .iter()
+                    .filter(|check| check.foreground.starts_with("on_"))
+                {
+                    assert!(
+                        check.ratio >= threshold,
+                        "{hex} {mode:?} {} on {} is {:.2}",
+                        check.foreground,
+                        check.background,
+                        check.ratio
+                    );
+                }
+            }
         }
     }
 
-    fn relative_luminance((r, g, b): (f32, f32, f32)) -> f32 {
-        0.2126 * r + 0.7152 * g + 0.0722 * b
+    #[test]
+    fn seed_accent_overrides_derived_hue() {
+        let derived = PaletteSeed::new("#2563eb").unwrap();
+        let branded = derived.with_accent("#f472b6").unwrap();
+        assert_ne!(
+            derived.palette(PaletteMode::Light).accent,
+            branded.palette(PaletteMode::Light).accent
+        );
+        assert!(matches!(
+            PaletteSeed::new("blue"),
+            Err(PaletteError::InvalidSeed(seed)) if seed == "blue"
+        ));
+        assert!(matches!(
+            PaletteSeed::new("#2563eb80"),
+            Err(PaletteError::InvalidSeed(seed)) if seed == "#2563eb80"
+        ));
+        assert!(matches!(
+            derived.with_accent("#f472b6ff"),
+            Err(PaletteError::InvalidSeed(_))
+        ));
     }
 }
@@ -0,0 +1,155 @@
+//! Animated palette transitions between themes.
+
+use std::time::Duration;
+#[cfg(feature = "gpui")]
+use std::{sync::Arc, time::Instant};
+
+#[cfg(feature = "gpui")]
+use gpui::App;
+
+#[cfg(feature = "gpui")]
+use crate::theme::{ThemeDefinition, ThemeRegistry};
+
+/// Delay between transition frames, roughly one per display refresh.
+#[cfg(feature = "gpui")]
+const FRAME_INTERVAL: Duration = Duration::from_millis(16);
+
+/// How [`ThemeRegistry::apply`] moves from one theme to the next.
+///
+/// Animated transitions blend every palette color from the outgoing theme to
+/// the incoming one; typography, spacing and elevations switch on the first
+/// frame.
+///
+/// ```
+/// use std::time::Duration;
+///
+/// use designsystem::ThemeTransition;
+///
+/// let transition = ThemeTransition::fade(Duration::from_millis(200));
+/// assert!(transition.is_animated());
+/// assert!(!transition.with_reduced_motion(true).is_animated());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ThemeTransition {
+    /// Time taken to blend from the old palette to the new one.
+    pub duration: Duration,
+    /// Set when the user prefers reduced motion, in which case themes switch
+    /// instantly whatever the duration.
+    pub reduced_motion: bool,
+}
+
+impl ThemeTransition {
+    /// Switches themes on the next frame. This is the registry default.
+    pub const INSTANT: Self = Self::fade(Duration::ZERO);
+
+    /// Duration used by the workbench, short enough not to delay input.
+    pub const DEFAULT_DURATION: Duration = Duration::from_millis(250);
+
+    /// Blends palettes over `duration`.
+    #[must_use]
+    pub const fn fade(duration: Duration) -> Self {
+        Self {
+            duration,
+            reduced_motion: false,
+        }
+    }
+
+    /// Returns the same transition with the reduced-motion preference set.
+    #[must_use]
+    pub const fn with_reduced_motion(self, reduced_motion: bool) -> Self {
+        Self {
+            reduced_motion,
+            ..self
+        }
+    }
+
+    /// Returns `true` when theme changes animate.
+    #[must_use]
+    pub const fn is_animated(self) -> bool {
+        !self.reduced_motion && !self.duration.is_zero()
+    }
+
+    /// Returns the eased blend amount after `elapsed`, from 0.0 to 1.0.
+    #[cfg(any(test, feature = "gpui"))]
+    fn progress(self, elapsed: Duration) -> f32 {
+        if !self.is_animated() {
+            return 1.0;
+        }
+        let linear = (elapsed.as_secs_f32() / self.duration.as_secs_f32()).min(1.0);
+        // Smoothstep, so the fade starts and settles gently.
+        linear * linear * (3.0 - 2.0 * linear)
+    }
+}
+
+impl Default for ThemeTransition {
+    fn default() -> Self {
+        Self::INSTANT
+    }
+}
+
+#[cfg(feature = "gpui")]
+impl ThemeRegistry {
+    /// Blends from `from` to `to`, showing a new frame in every window that
+    /// follows the app-wide theme until the transition completes or another
+    /// theme is applied.
+    pub(crate) fn animate(
+        &self,
+        epoch: u64,
+        from: &Arc<ThemeDefinition>,
+        to: Arc<ThemeDefinition>,
+        transition: ThemeTransition,
+        cx: &mut App,
+    ) {
+        self.show(Arc::new(ThemeDefinition::blend(from, &to, 0.0)), cx);
+
+        let registry = self.clone();
+        let from = from.clone();
+        let started = Instant::now();
+        cx.spawn(async move |cx| loop {
+            cx.background_executor().timer(FRAME_INTERVAL).await;
+
+            let progress = transition.progress(started.elapsed());
+            let finished = progress >= 1.0;
+            let updated = cx.update(|cx| {
+                if !registry.is_current_transition(epoch) {
+                    return false;
+                }
+                let frame = if finished {
+                    to.clone()
+                } else {
+                    Arc::new(ThemeDefinition::blend(&from, &to, progress))
+                };
+                registry.show(frame, cx);
+                cx.refresh_windows();
+                true
+            });
+            if finished || !matches!(updated, Ok(true)) {
+                return;
+            }
+        })
+        .detach();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn progress_eases_to_completion() {
+        let transition = ThemeTransition::fade(Duration::from_millis(200));
+        assert!(transition.progress(Duration::ZERO).abs() < f32::EPSILON);
+        assert!((transition.progress(Duration::from_millis(100)) - 0.5).abs() < 1e-6);
+        assert!(transition.progress(Duration::from_millis(50)) < 0.25);
+        assert!((transition.progress(Duration::from_secs(1)) - 1.0).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn reduced_motion_disables_animation() {
+        let transition =
+            ThemeTransition::fade(Duration::from_millis(200)).with_reduced_motion(true);
+        assert!(!transition.is_animated());
+        assert!((transition.progress(Duration::ZERO) - 1.0).abs() < f32::EPSILON);
+        assert!(!ThemeTransition::default().is_animated());
+    }
+}
@@ -0,0 +1,321 @@
+use std::{fs, io, path::Path};
+
+use resvg::{
+    tiny_skia::{Pixmap, PixmapPaint, Transform},
+    usvg::{Options, Tree},
+};
+use serde::Serialize;
+use thiserror::Error;
+
+/// Largest icon size accepted, in pixels.
+const MAX_SIZE: u32 = 1024;
+
+/// Errors that can occur while rasterizing icons.
+#[derive(Debug, Error)]
+pub enum SpriteError {
+    /// Errors bubbling up from file system interactions.
+    #[error("failed to write icon sprites: {0}")]
+    Io(#[from] io::Error),
+    /// A requested size is zero or larger than 1024 pixels.
+    #[error("invalid icon size {0}: expected 1 to 1024 pixels")]
+    InvalidSize(u32),
+    /// A requested color is not a hex color.
+    #[error("invalid color '{0}': expected #rgb, #rrggbb or #rrggbbaa")]
+    InvalidColor(String),
+    /// An icon's markup could not be parsed.
+    #[error("failed to parse icon '{icon}': {message}")]
+    Svg {
+        /// Name of the icon.
+        icon: String,
+        /// Parser error message.
+        message: String,
+    },
+    /// A rendered bitmap could not be encoded as PNG.
+    #[error("failed to encode '{0}' as PNG")]
+    Encode(String),
+}
+
+/// Sizes, colors and layout of the rendered bitmaps.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SpriteOptions {
+    /// Square pixel sizes to render every icon at.
+    pub sizes: Vec<u32>,
+    /// Hex colors substituted for `currentColor`. Icons that paint their own
+    /// colors look the same in every color.
+    pub colors: Vec<String>,
+    /// Transparent pixels kept around each frame in the sprite sheet so
+    /// filtering does not bleed neighbours into each other.
+    pub padding: u32,
+}
+
+impl Default for SpriteOptions {
+    fn default() -> Self {
+        Self {
+            sizes: vec![16, 24, 32],
+            colors: vec!["#000000".to_owned()],
+            padding: 1,
+        }
+    }
+}
+
+/// Index of a generated sprite sheet, written next to it as `sprites.json`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SpriteSheet {
+    /// File name of the sheet, relative to the index.
+    pub image: String,
+    /// Width of the sheet in pixels.
+    pub width: u32,
+    /// Height of the sheet in pixels.
+    pub height: u32,
+    /// Every rendered bitmap, in icon, size, then color order.
+    pub frames: Vec<SpriteFrame>,
+}
+
+/// One rendered bitmap and its place in the sprite sheet.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SpriteFrame {
+    /// Unique key, `<icon>-<size>-<color without #>`.
+    pub id: String,
+    /// Icon name, such as `search`.
+    pub icon: String,
+    /// Rendered size in pixels.
+    pub size: u32,
+    /// Color substituted for `currentColor`.
+    pub color: String,
+    /// Standalone PNG, relative to the index.
+    pub file: String,
+    /// Left edge in the sheet.
+    pub x: u32,
+    /// Top edge in the sheet.
+    pub y: u32,
+    /// Width in pixels.
+    pub width: u32,
+    /// Height in pixels.
+    pub height: u32,
+}
+
+/// Rasterizes every `(name, svg)` icon at each size and color of `options`.
+///
+/// Writes one PNG per combination to `out_dir/png/<id>.png`, packs them all
+/// into `out_dir/sprites.png`, and describes the layout in
+/// `out_dir/sprites.json`, which is also returned.
+///
+/// # Errors
+///
+/// Returns [`SpriteError::InvalidSize`] or [`SpriteError::InvalidColor`]
+/// for bad options, [`SpriteError::Svg`] for markup that does not parse,
+/// and [`SpriteError::Io`] when the output cannot be written.
+pub fn generate_icon_sprites<P>(
+    icons: &[(&str, &str)],
+    options: &SpriteOptions,
+    out_dir: P,
+) -> Result<SpriteSheet, SpriteError>
+where
+    P: AsRef<Path>,
+{
+    if let Some(size) = options
+        .sizes
+        .iter()
+        .find(|size| **size == 0 || **size > MAX_SIZE)
+    {
+        return Err(SpriteError::InvalidSize(*size));
+    }
+    if let Some(color) = options.colors.iter().find(|color| !is_hex_color(color)) {
+        return Err(SpriteError::InvalidColor(color.clone()));
+    }
+
+    let out_dir = out_dir.as_ref();
+    let png_dir = out_dir.join("png");
+    fs::create_dir_all(&png_dir)?;
+
+    let mut frames = Vec::new();
+    let mut bitmaps = Vec::new();
+    for (name, svg) in icons {
+        for size in &options.sizes {
+            for color in &options.colors {
+                let pixmap = render(name, &svg.replace("currentColor", color), *size)?;
+                let id = format!("{name}-{size}-{}", color.trim_start_matches('#'));
+                let file = format!("png/{id}.png");
+                let png = pixmap
+                    .encode_png()
+                    .map_err(|_| SpriteError::Encode(id.clone()))?;
+                fs::write(out_dir.join(&file), png)?;
+                frames.push(SpriteFrame {
+                    id,
+                    icon: (*name).to_owned(),
+                    size: *size,
+                    color: color.clone(),
+                    file,
+                    x: 0,
+                    y: 0,
+                    width: pixmap.width(),
+                    height: pixmap.height(),
+                });
+                bitmaps.push(pixmap);
+            }
+        }
+    }
+
+    let (width, height) = pack(&mut frames, options.padding);
+    let mut sheet = Pixmap::new(width.max(1), height.max(1))
+        .ok_or_else(|| SpriteError::Encode("sprites.png".to_owned()))?;
+    for (frame, bitmap) in frames.iter().zip(&bitmaps) {
+        #[allow(clippy::cast_possible_wrap)]
+        sheet.draw_pixmap(
+            frame.x as i32,
+            frame.y as i32,
+            bitmap.as_ref(),
+            &PixmapPaint::default(),
+            Transform::identity(),
+            None,
+        );
+    }
+    let png = sheet
+        .encode_png()
+        .map_err(|_| SpriteError::Encode("sprites.png".to_owned()))?;
+    fs::write(out_dir.join("sprites.png"), png)?;
+
+    let index = SpriteSheet {
+        image: "sprites.png".to_owned(),
+        width,
+        height,
+        frames,
+    };
+    let json = serde_json::to_vec_pretty(&index).map_err(io::Error::from)?;
+    fs::write(out_dir.join("sprites.json"), json)?;
+    Ok(index)
+}
+
+/// Draws `svg` scaled to fit a `size` pixel square, centered.
+fn render(name: &str, svg: &str, size: u32) -> Result<Pixmap, SpriteError> {
+    let tree = Tree::from_str(svg, &Options::default()).map_err(|err| SpriteError::Svg {
+        icon: name.to_owned(),
+        message: err.to_string(),
+    })?;
+    let mut pixmap = Pixmap::new(size, size).ok_or(SpriteError::InvalidSize(size))?;
+    let view = tree.size();
+    #[allow(clippy::cast_precision_loss)]
+    let size = size as f32;
+    let scale = size / view.width().max(view.height());
+    let transform = Transform::from_scale(scale, scale).post_translate(
+        (size - view.width() * scale) / 2.0,
+        (size - view.height() * scale) / 2.0,
+    );
+    resvg::render(&tree, transform, &mut pixmap.as_mut());
+    Ok(pixmap)
+}
+
+/// Places `frames` on shelves, tallest first, and returns the sheet size.
+///
+/// The sheet is about as wide as the square root of the total frame area,
+/// which keeps it close to square.
+fn pack(frames: &mut [SpriteFrame], padding: u32) -> (u32, u32) {
+    let padded = |length: u32| length + padding * 2;
+    let area: u64 = frames
+        .iter()
+        .map(|frame| u64::from(padded(frame.width)) * u64::from(padded(frame.height)))
+        .sum();
+    let widest = frames
+        .iter()
+        .map(|frame| padded(frame.width))
+        .max()
+        .unwrap_or(0);
+    #[allow(
+        clippy::cast_possible_truncation,
+        clippy::cast_precision_loss,
+        clippy::cast_sign_loss
+    )]
+    let target_width = ((area as f64).sqrt().ceil() as u32).max(widest);
+
+    let mut order: Vec<usize> = (0..frames.len()).collect();
+    order.sort_by_key(|&index| std::cmp::Reverse(frames[index].height));
+
+    let (mut x, mut y, mut shelf_height, mut width) = (0, 0, 0, 0);
+    for index in order {
+        let frame = &mut frames[index];
+        if x + padded(frame.width) > target_width {
+            x = 0;
+            y += shelf_height;
+            shelf_height = 0;
+        }
+        frame.x = x + padding;
+        frame.y = y + padding;
+        x += padded(frame.width);
+        width = width.max(x);
+        shelf_height = shelf_height.max(padded(frame.height));
+    }
+    (width, y + shelf_height)
+}
+
+fn is_hex_color(color: &str) -> bool {
+    color.strip_prefix('#').is_some_and(|hex| {
+        matches!(hex.len(), 3 | 6 | 8) && hex.chars().all(|ch| ch.is_ascii_hexdigit())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SQUARE: &str = "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"0 0 24 24\">\
+                          <rect width=\"24\" height=\"24\" fill=\"currentColor\"/></svg>";
+    const DOT: &str = "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"0 0 24 24\">\
+                       <circle cx=\"12\" cy=\"12\" r=\"4\" fill=\"#f59e0b\"/></svg>";
+
+    #[test]
+    fn sheet_packs_every_frame_without_overlap() {
+        let dir = tempfile::tempdir().unwrap();
+        let options = SpriteOptions {
+            sizes: vec![16, 24],
+            colors: vec!["#ff0000".into(), "#0000ff".into()],
+            padding: 1,
+        };
+        let sheet =
+            generate_icon_sprites(&[("square", SQUARE), ("dot", DOT)], &options, dir.path())
+                .unwrap();
+
+        assert_eq!(sheet.frames.len(), 8);
+        for (ix, a) in sheet.frames.iter().enumerate() {
+            assert!(a.x + a.width <= sheet.width && a.y + a.height <= sheet.height);
+            assert!(dir.path().join(&a.file).exists());
+            for b in &sheet.frames[ix + 1..] {
+                let apart = a.x + a.width <= b.x
+                    || b.x + b.width <= a.x
+                    || a.y + a.height <= b.y
+                    || b.y + b.height <= a.y;
+                assert!(apart, "{} overlaps {}", a.id, b.id);
+            }
+        }
+        assert!(dir.path().join("sprites.png").exists());
+        let index = fs::read_to_string(dir.path().join("sprites.json")).unwrap();
+        assert!(index.contains("\"id\": \"square-24-ff0000\""));
+    }
+
+    #[test]
+    fn current_color_is_replaced() {
+        let pixmap = render("square", &SQUARE.replace("currentColor", "#0000ff"), 8).unwrap();
+        let pixel = pixmap.pixel(4, 4).unwrap();
+        assert_eq!((pixel.red(), pixel.blue()), (0, 255));
+    }
+
+    #[test]
+    fn rejects_invalid_options() {
+        let dir = tempfile::tempdir().unwrap();
+        let options = SpriteOptions {
+            colors: vec!["red".into()],
+            ..SpriteOptions::default()
+        };
+        assert!(matches!(
+            generate_icon_sprites(&[("square", SQUARE)], &options, dir.path()),
+            Err(SpriteError::InvalidColor(_))
+        ));
+        let options = SpriteOptions {
+            sizes: vec![0],
+            ..SpriteOptions::default()
+        };
+        assert!(matches!(
+            generate_icon_sprites(&[("square", SQUARE)], &options, dir.path()),
+            Err(SpriteError::InvalidSize(0))
+        ));
+    }
+}
@@ -0,0 +1,125 @@
+//! File watcher that tells running applications when another process saves
+//! the workspace configuration.
+
+use std::{
+    path::Path,
+    sync::mpsc::{channel, TryRecvError},
+    time::Duration,
+};
+
+use gpui::{App, Task};
+use notify::{EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+
+use crate::{ConfigError, ConfigStore, WorkspaceConfig};
+
+/// How often queued file system events are drained on the foreground thread.
+/// An atomic save produces several events, so batching them reloads the file
+/// once.
+const POLL_INTERVAL: Duration = Duration::from_millis(200);
+
+/// Handle that keeps a configuration file watch alive.
+///
+/// Dropping the handle stops watching; call [`ConfigWatcher::detach`] to keep
+/// watching for the rest of the process.
+#[must_use = "the configuration file is only watched while the handle is alive"]
+pub struct ConfigWatcher {
+    watcher: RecommendedWatcher,
+    task: Task<()>,
+}
+
+impl ConfigWatcher {
+    /// Keeps the watcher running until the application exits.
+    pub fn detach(self) {
+        self.task.detach();
+        std::mem::forget(self.watcher);
+    }
+}
+
+impl ConfigStore {
+    /// Watches the configuration file and calls `on_change` with the new
+    /// contents whenever another process saves it.
+    ///
+    /// Saves made through this store or its clones are not reported, since
+    /// the process already has those values. Reload failures are passed to
+    /// `on_error` instead of interrupting the watch.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ConfigError::Watch`] when the platform watcher cannot be
+    /// created or the directory cannot be watched, and [`ConfigError::Io`]
+    /// when the directory cannot be created.
+    pub fn watch(
+        &self,
+        cx: &mut App,
+        on_change: impl Fn(&WorkspaceConfig, &mut App) + 'static,
+        on_error: impl Fn(&ConfigError, &mut App) + 'static,
+    ) -> Result<ConfigWatcher, ConfigError> {
+        self.ensure_dir()?;
+        let (tx, rx) = channel();
+        let mut watcher = notify::recommended_watcher(move |event| {
+            let _ = tx.send(event);
+        })?;
+        // Saves rename a new file over the old one, so watch the directory
+        // rather than the file itself.
+        let dir = self
+            .path()
+            .parent()
+            .filter(|parent| !parent.as_os_str().is_empty())
+            .unwrap_or_else(|| Path::new("."));
+        watcher.watch(dir, RecursiveMode::NonRecursive)?;
+
+        let store = self.clone();
+        let file_name = self.path().file_name().map(ToOwned::to_owned);
+        let task = cx.spawn(async move |cx| loop {
+            cx.background_executor().timer(POLL_INTERVAL).await;
+
+            let mut changed = false;
+            let mut errors = Vec::new();
+            loop {
+                match rx.try_recv() {
+                    Ok(Ok(event)) => {
+                        changed |= !matches!(event.kind, EventKind::Access(_))
+                            && event
+                                .paths
+                                .iter()
+                                .any(|path| path.file_name() == file_name.as_deref());
+                    }
+                    Ok(Err(err)) => errors.push(ConfigError::Watch(err)),
+                    Err(TryRecvError::Empty) => break,
+                    Err(TryRecvError::Disconnected) => return,
+                }
+            }
+            let mut config = None;
+            if changed {
+                // Reading takes the config lock, which another process may
+                // hold, so keep it off the foreground thread.
+                let store = store.clone();
+                let reloaded = cx
+                    .background_executor()
+                    .spawn(async move { store.reload_if_changed() })
+                    .await;
+                match reloaded {
+                    Ok(reloaded) => config = reloaded,
+                    Err(err) => errors.push(err),
+                }
+            }
+            if config.is_none() && errors.is_empty() {
+                continue;
+            }
+
+            let updated = cx.update(|cx| {
+                for err in &errors {
+                    on_error(err, cx);
+                }
+                if let Some(config) = &config {
+                    on_change(config, cx);
+                }
+            });
+            if updated.is_err() {
+                return;
+            }
+        });
+
+        Ok(ConfigWatcher { watcher, task })
+    }
+}
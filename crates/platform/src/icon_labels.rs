@@ -0,0 +1,59 @@
+//! Translations of the accessible icon labels shared by every application.
+
+use designsystem::IconLabels;
+use unic_langid::LanguageIdentifier;
+
+use crate::LocalizationRegistry;
+
+/// Spanish labels for the `icon.*` keys. English needs no entries because
+/// unresolved keys fall back to the label in each icon's metadata.
+const SPANISH_ICON_LABELS: [(&str, &str); 8] = [
+    ("icon.activity", "Actividad"),
+    ("icon.product-accessibility", "Accesibilidad"),
+    ("icon.product-localization", "Localización"),
+    ("icon.product-palette", "Paleta"),
+    ("icon.product-shortcuts", "Atajos"),
+    ("icon.search", "Buscar"),
+    ("icon.sparkles", "Destellos"),
+    ("icon.users", "Usuarios"),
+];
+
+impl LocalizationRegistry {
+    /// Registers the bundled translations of the `icon.*` label keys.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the localization mutex was poisoned.
+    pub fn register_icon_labels(&self) {
+        let spanish: LanguageIdentifier = "es-ES".parse().expect("valid locale");
+        self.register_messages(spanish, SPANISH_ICON_LABELS);
+    }
+
+    /// Resolves every icon's accessible label in `locale`, ready to
+    /// [`install`](IconLabels::install) whenever the locale changes.
+    #[must_use]
+    pub fn icon_labels(&self, locale: &LanguageIdentifier) -> IconLabels {
+        IconLabels::localized(|key| self.translate(locale, key))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use designsystem::IconName;
+
+    use super::*;
+
+    #[test]
+    fn icon_labels_follow_the_locale() {
+        let english: LanguageIdentifier = "en-US".parse().unwrap();
+        let registry = LocalizationRegistry::new(english.clone());
+        registry.register_icon_labels();
+
+        let spanish = registry.icon_labels(&"es-ES".parse().unwrap());
+        assert_eq!(spanish.label(IconName::Search), "Buscar");
+        assert_eq!(
+            registry.icon_labels(&english).label(IconName::Search),
+            IconName::Search.metadata().label
+        );
+    }
+}
@@ -3,18 +3,25 @@
 
 use std::{
     collections::HashMap,
-    fs,
+    fmt,
+    fs::{self, File},
+    io::Write,
     path::{Path, PathBuf},
+    process,
     sync::{Arc, Mutex},
 };
 
 mod assets;
 pub use assets::{AssetBundle, AssetSpec, EMBEDDED_ASSETS};
+mod config_watch;
+pub use config_watch::ConfigWatcher;
+mod icon_labels;
+mod migrations;
 
 use chrono::{DateTime, Utc};
 use crossbeam_channel::{unbounded, Receiver, Sender};
 use directories::ProjectDirs;
-use gpui::App;
+use gpui::{App, Task};
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 use unic_langid::LanguageIdentifier;
@@ -111,9 +118,17 @@ impl Default for BenchmarkRunRecord {
     }
 }
 
+/// Schema version written into every saved [`WorkspaceConfig`].
+///
+/// Files from older versions are migrated when loaded; files from newer
+/// versions are rejected with [`ConfigError::UnsupportedVersion`].
+pub const CONFIG_SCHEMA_VERSION: u32 = 2;
+
 /// Persistent workspace configuration stored as JSON.
-#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct WorkspaceConfig {
+    /// Schema version of the file, [`CONFIG_SCHEMA_VERSION`] once loaded.
+    pub version: u32,
     /// Serialized window geometry such as size and position.
     pub window_state: Option<String>,
     /// Dock layout serialized by the component gallery.
@@ -123,6 +138,27 @@ pub struct WorkspaceConfig {
     /// Historical performance benchmark runs.
     #[serde(default)]
     pub benchmark_runs: Vec<BenchmarkRunRecord>,
+    /// Theme preference slug such as `system` or `dark`, as written by
+    /// `ThemePreference`'s `Display` implementation.
+    #[serde(default)]
+    pub theme_preference: Option<String>,
+    /// Whether theme changes should switch instantly instead of animating.
+    #[serde(default)]
+    pub reduce_motion: bool,
+}
+
+impl Default for WorkspaceConfig {
+    fn default() -> Self {
+        Self {
+            version: CONFIG_SCHEMA_VERSION,
+            window_state: None,
+            layout_state: None,
+            recent_workspaces: Vec::new(),
+            benchmark_runs: Vec::new(),
+            theme_preference: None,
+            reduce_motion: false,
+        }
+    }
 }
 
 impl WorkspaceConfig {
@@ -155,12 +191,102 @@ pub enum ConfigError {
     /// Wraps JSON serialization issues.
     #[error("serialization error: {0}")]
     Serialization(#[from] serde_json::Error),
+    /// Raised when the file was written by a newer release.
+    #[error("unsupported config version {found}; this build reads up to version {supported}")]
+    UnsupportedVersion {
+        /// Version recorded in the file.
+        found: u32,
+        /// Newest version this build understands.
+        supported: u32,
+    },
+    /// Raised when the configuration file cannot be watched.
+    #[error("file watch error: {0}")]
+    Watch(#[from] notify::Error),
+}
+
+/// Number of previous files [`ConfigStore::save`] keeps unless configured
+/// with [`ConfigStore::with_backups`].
+pub const DEFAULT_CONFIG_BACKUPS: usize = 3;
+
+/// Configuration returned by [`ConfigStore::load_or_recover`] and
+/// [`bootstrap`].
+#[derive(Debug)]
+pub struct LoadedConfig {
+    /// The stored configuration, or defaults when the file was unreadable.
+    pub config: WorkspaceConfig,
+    /// Backup the configuration was restored from because the file was
+    /// truncated, if it was.
+    pub restored_from: Option<PathBuf>,
+    /// Where an unreadable file was moved, if one was.
+    pub unreadable: Option<PathBuf>,
+    /// Error that made loading fall back to defaults without touching the
+    /// file, such as [`ConfigError::UnsupportedVersion`] for a file written
+    /// by a newer release.
+    pub error: Option<ConfigError>,
+}
+
+impl LoadedConfig {
+    /// Describes every recovery step taken while loading, for the
+    /// application to log or show.
+    #[must_use]
+    pub fn warnings(&self) -> Vec<String> {
+        let mut warnings = Vec::new();
+        if let Some(backup) = &self.restored_from {
+            warnings.push(format!(
+                "workspace configuration was truncated; restored it from {}",
+                backup.display()
+            ));
+        }
+        if let Some(unreadable) = &self.unreadable {
+            warnings.push(format!(
+                "workspace configuration was unreadable; moved it to {} and started from defaults",
+                unreadable.display()
+            ));
+        }
+        match &self.error {
+            Some(err @ ConfigError::UnsupportedVersion { .. }) => warnings.push(format!(
+                "workspace configuration was written by a newer release ({err}); \
+                 using defaults and leaving the file unchanged"
+            )),
+            Some(err) => warnings.push(format!(
+                "failed to load workspace configuration, using defaults: {err}"
+            )),
+            None => {}
+        }
+        warnings
+    }
 }
 
 /// Reader/writer responsible for persisting [`WorkspaceConfig`].
-#[derive(Debug, Clone)]
+///
+/// Saves replace the file atomically and keep the previous files as
+/// `<name>.bak.1` (newest) through `<name>.bak.<n>`. Every read and write
+/// holds an advisory lock on `<name>.lock`, so several processes sharing the
+/// file take turns; use [`ConfigStore::update`] to change some fields without
+/// overwriting what other processes saved in the meantime. Waiting for that
+/// lock blocks, so applications update through
+/// [`ConfigStore::update_in_background`] from the UI thread.
+#[derive(Clone)]
 pub struct ConfigStore {
     path: PathBuf,
+    backups: usize,
+    /// Contents this store last read or wrote, shared between clones so
+    /// [`ConfigStore::watch`] can skip the process's own saves.
+    known: Arc<Mutex<Option<Vec<u8>>>>,
+    /// Changes waiting for [`ConfigStore::update_in_background`] to save
+    /// them, shared between clones so they are applied in the order queued.
+    queued: Arc<Mutex<Vec<QueuedChange>>>,
+}
+
+type QueuedChange = Box<dyn FnOnce(&mut WorkspaceConfig) + Send>;
+
+impl fmt::Debug for ConfigStore {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ConfigStore")
+            .field("path", &self.path)
+            .field("backups", &self.backups)
+            .finish_non_exhaustive()
+    }
 }
 
 impl Default for ConfigStore {
@@ -173,7 +299,20 @@ impl ConfigStore {
     /// Creates a store rooted at the given path.
     #[must_use]
     pub fn new(path: PathBuf) -> Self {
-        Self { path }
+        Self {
+            path,
+            backups: DEFAULT_CONFIG_BACKUPS,
+            known: Arc::default(),
+            queued: Arc::default(),
+        }
+    }
+
+    /// Sets how many previous files [`ConfigStore::save`] keeps; `0`
+    /// disables backups.
+    #[must_use]
+    pub fn with_backups(mut self, backups: usize) -> Self {
+        self.backups = backups;
+        self
     }
 
     fn ensure_dir(&self) -> Result<(), ConfigError> {
@@ -183,24 +322,357 @@ impl ConfigStore {
         Ok(())
     }
 
+    /// Returns the path next to the config file with `suffix` appended to
+    /// its name.
+    fn sibling(&self, suffix: &str) -> PathBuf {
+        let mut file_name = self.path.file_name().unwrap_or_default().to_os_string();
+        file_name.push(suffix);
+        self.path.with_file_name(file_name)
+    }
+
+    fn backup_path(&self, index: usize) -> PathBuf {
+        self.sibling(&format!(".bak.{index}"))
+    }
+
+    /// Blocks until this process holds the exclusive lock on `<name>.lock`,
+    /// which is released when the returned file is dropped.
+    ///
+    /// The lock is separate from the config file because saves replace that
+    /// file. Locks are per open file, so a store must not be used again while
+    /// one of its own locks is held. Another process can hold the lock for as
+    /// long as it likes, so the GPUI main thread only takes it through
+    /// [`ConfigStore::update_in_background`] and [`ConfigStore::watch`].
+    fn lock(&self) -> Result<File, ConfigError> {
+        self.ensure_dir()?;
+        let lock = File::options()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(self.sibling(".lock"))?;
+        lock.lock()?;
+        Ok(lock)
+    }
+
+    fn remember(&self, bytes: &[u8]) {
+        *self.known.lock().expect("config store mutex poisoned") = Some(bytes.to_vec());
+    }
+
     /// Loads the workspace configuration or returns a default value.
+    ///
+    /// Files written with an older schema are migrated to
+    /// [`CONFIG_SCHEMA_VERSION`]; the file on disk is only rewritten by the
+    /// next [`ConfigStore::save`]. A file cut short by an interrupted write
+    /// is replaced with the newest backup that loads.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ConfigError::Io`] when the file cannot be read,
+    /// [`ConfigError::Serialization`] when it is not a valid configuration
+    /// and no backup could replace it, and
+    /// [`ConfigError::UnsupportedVersion`] when a newer release wrote it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the config store mutex was poisoned.
     pub fn load(&self) -> Result<WorkspaceConfig, ConfigError> {
+        let _lock = self.lock()?;
+        self.read().map(|(config, _)| config)
+    }
+
+    /// Reads the file, restoring the newest valid backup over a truncated
+    /// one, and returns the backup used.
+    fn read(&self) -> Result<(WorkspaceConfig, Option<PathBuf>), ConfigError> {
         if !self.path.exists() {
-            return Ok(WorkspaceConfig::default());
+            return Ok((WorkspaceConfig::default(), None));
         }
         let bytes = fs::read(&self.path)?;
-        let config = serde_json::from_slice(&bytes)?;
-        Ok(config)
+        match parse_config(&bytes) {
+            Err(ConfigError::Serialization(err)) if is_truncated(&bytes, &err) => {
+                for backup in self.backups() {
+                    let Ok(backup_bytes) = fs::read(&backup) else {
+                        continue;
+                    };
+                    if let Ok(config) = parse_config(&backup_bytes) {
+                        self.write_atomic(&backup_bytes)?;
+                        return Ok((config, Some(backup)));
+                    }
+                }
+                Err(ConfigError::Serialization(err))
+            }
+            result => {
+                let config = result?;
+                self.remember(&bytes);
+                Ok((config, None))
+            }
+        }
     }
 
-    /// Persists the workspace configuration to disk.
+    /// Reloads the file if its contents differ from what this store last
+    /// read or wrote.
+    pub(crate) fn reload_if_changed(&self) -> Result<Option<WorkspaceConfig>, ConfigError> {
+        let _lock = self.lock()?;
+        let bytes = match fs::read(&self.path) {
+            Ok(bytes) => bytes,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(err) => return Err(err.into()),
+        };
+        let known = self.known.lock().expect("config store mutex poisoned");
+        if known.as_deref() == Some(bytes.as_slice()) {
+            return Ok(None);
+        }
+        drop(known);
+        self.read().map(|(config, _)| Some(config))
+    }
+
+    /// Loads the workspace configuration like [`ConfigStore::load`], but
+    /// falls back to defaults instead of failing when the file cannot be
+    /// used.
+    ///
+    /// A file that cannot be parsed or restored from a backup is renamed to
+    /// `<name>.unreadable-<timestamp>` in the same directory so settings can
+    /// still be recovered by hand. A file written by a newer release is left
+    /// in place and reported in [`LoadedConfig::error`]; saves refuse to
+    /// replace it, so that release keeps its settings.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ConfigError::Io`] when the file cannot be read or moved.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the config store mutex was poisoned.
+    pub fn load_or_recover(&self) -> Result<LoadedConfig, ConfigError> {
+        let _lock = self.lock()?;
+        match self.read() {
+            Ok((config, restored_from)) => Ok(LoadedConfig {
+                config,
+                restored_from,
+                unreadable: None,
+                error: None,
+            }),
+            Err(err @ (ConfigError::Io(_) | ConfigError::Watch(_))) => Err(err),
+            Err(err @ ConfigError::UnsupportedVersion { .. }) => Ok(LoadedConfig {
+                config: WorkspaceConfig::default(),
+                restored_from: None,
+                unreadable: None,
+                error: Some(err),
+            }),
+            Err(ConfigError::Serialization(_)) => {
+                let unreadable = self.sibling(&format!(
+                    ".unreadable-{}",
+                    Utc::now().format("%Y%m%dT%H%M%S%.3fZ")
+                ));
+                fs::rename(&self.path, &unreadable)?;
+                Ok(LoadedConfig {
+                    config: WorkspaceConfig::default(),
+                    restored_from: None,
+                    unreadable: Some(unreadable),
+                    error: None,
+                })
+            }
+        }
+    }
+
+    /// Persists the workspace configuration to disk, replacing whatever
+    /// other processes saved; prefer [`ConfigStore::update`] when only some
+    /// fields changed.
+    ///
+    /// The previous file is rotated into the backups, then the new contents
+    /// are written to a temporary file, flushed to disk, and renamed over the
+    /// old one, so a crash leaves either the old or the new file in place.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ConfigError::Io`] when the file or a backup cannot be
+    /// written, and [`ConfigError::UnsupportedVersion`] when the file on disk
+    /// was written by a newer release.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the config store mutex was poisoned.
     pub fn save(&self, config: &WorkspaceConfig) -> Result<(), ConfigError> {
-        self.ensure_dir()?;
+        let _lock = self.lock()?;
+        self.write(config)
+    }
+
+    /// Re-reads the file, applies `change` to it, and saves the result, all
+    /// while holding the lock, then returns the saved configuration.
+    ///
+    /// Fields `change` leaves alone keep the values other processes saved, so
+    /// applications should update through this instead of saving a copy of
+    /// the config they loaded at startup. Nothing is written when `change`
+    /// leaves the configuration as it was. `change` must not use the store.
+    ///
+    /// This waits for other processes to release the lock; call
+    /// [`ConfigStore::update_in_background`] instead from the UI thread.
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors of [`ConfigStore::load`] and [`ConfigStore::save`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the config store mutex was poisoned.
+    pub fn update(
+        &self,
+        change: impl FnOnce(&mut WorkspaceConfig),
+    ) -> Result<WorkspaceConfig, ConfigError> {
+        let _lock = self.lock()?;
+        self.change_locked(change)
+    }
+
+    /// Queues `change` and saves it like [`ConfigStore::update`] on the
+    /// background executor, so waiting for the lock never stalls the UI.
+    ///
+    /// Changes queued through a store and its clones are saved in the order
+    /// they were queued, and the task resolves to a configuration that
+    /// includes `change` and everything queued before it.
+    ///
+    /// # Errors
+    ///
+    /// The task fails with the errors of [`ConfigStore::update`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the config store mutex was poisoned.
+    pub fn update_in_background(
+        &self,
+        cx: &App,
+        change: impl FnOnce(&mut WorkspaceConfig) + Send + 'static,
+    ) -> Task<Result<WorkspaceConfig, ConfigError>> {
+        self.queue(change);
+        let store = self.clone();
+        cx.background_executor()
+            .spawn(async move { store.apply_queued() })
+    }
+
+    fn queue(&self, change: impl FnOnce(&mut WorkspaceConfig) + Send + 'static) {
+        self.queued
+            .lock()
+            .expect("config store mutex poisoned")
+            .push(Box::new(change));
+    }
+
+    /// Saves every queued change in one update. The queue is taken while the
+    /// lock is held, so whichever task locks first applies the changes in
+    /// order and later tasks read its result.
+    fn apply_queued(&self) -> Result<WorkspaceConfig, ConfigError> {
+        let _lock = self.lock()?;
+        let changes =
+            std::mem::take(&mut *self.queued.lock().expect("config store mutex poisoned"));
+        self.change_locked(|config| {
+            for change in changes {
+                change(config);
+            }
+        })
+    }
+
+    /// The body of [`ConfigStore::update`], for callers already holding the
+    /// lock.
+    fn change_locked(
+        &self,
+        change: impl FnOnce(&mut WorkspaceConfig),
+    ) -> Result<WorkspaceConfig, ConfigError> {
+        let (mut config, _) = self.read()?;
+        let before = config.clone();
+        change(&mut config);
+        if config != before {
+            self.write(&config)?;
+        }
+        Ok(config)
+    }
+
+    fn write(&self, config: &WorkspaceConfig) -> Result<(), ConfigError> {
+        self.refuse_newer_file()?;
         let buffer = serde_json::to_vec_pretty(config)?;
-        fs::write(&self.path, buffer)?;
+        self.rotate_backups()?;
+        self.write_atomic(&buffer)
+    }
+
+    /// Fails when the file on disk was written by a newer release, so a save
+    /// cannot discard settings this build does not understand.
+    fn refuse_newer_file(&self) -> Result<(), ConfigError> {
+        let Ok(bytes) = fs::read(&self.path) else {
+            return Ok(());
+        };
+        match serde_json::from_slice(&bytes).map(migrations::migrate) {
+            Ok(Err(err @ ConfigError::UnsupportedVersion { .. })) => Err(err),
+            _ => Ok(()),
+        }
+    }
+
+    /// Shifts `.bak.<n>` to `.bak.<n + 1>`, dropping the oldest, and copies
+    /// the current file to `.bak.1`.
+    fn rotate_backups(&self) -> Result<(), ConfigError> {
+        if self.backups == 0 || !self.path.exists() {
+            return Ok(());
+        }
+        for index in (1..self.backups).rev() {
+            let backup = self.backup_path(index);
+            if backup.exists() {
+                fs::rename(&backup, self.backup_path(index + 1))?;
+            }
+        }
+        fs::copy(&self.path, self.backup_path(1))?;
+        Ok(())
+    }
+
+    fn write_atomic(&self, bytes: &[u8]) -> Result<(), ConfigError> {
+        let temp = self.sibling(&format!(".tmp.{}", process::id()));
+        let written = File::create(&temp)
+            .and_then(|mut file| {
+                file.write_all(bytes)?;
+                file.sync_all()
+            })
+            .and_then(|()| fs::rename(&temp, &self.path));
+        if let Err(err) = written {
+            let _ = fs::remove_file(&temp);
+            return Err(err.into());
+        }
+        // The rename itself is only durable once the directory is flushed.
+        #[cfg(unix)]
+        if let Some(parent) = self
+            .path
+            .parent()
+            .filter(|parent| !parent.as_os_str().is_empty())
+        {
+            File::open(parent)?.sync_all()?;
+        }
+        self.remember(bytes);
         Ok(())
     }
 
+    /// Returns the backups that exist on disk, newest first.
+    #[must_use]
+    pub fn backups(&self) -> Vec<PathBuf> {
+        (1..=self.backups)
+            .map(|index| self.backup_path(index))
+            .filter(|backup| backup.exists())
+            .collect()
+    }
+
+    /// Replaces the configuration with backup `index`, where `1` is the
+    /// newest, and returns it.
+    ///
+    /// The current file is rotated into the backups like any other save, so
+    /// the restore can itself be undone.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ConfigError::Io`] when the backup does not exist or the
+    /// configuration cannot be written, and the errors of
+    /// [`ConfigStore::load`] when the backup is not a valid configuration.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the config store mutex was poisoned.
+    pub fn restore_backup(&self, index: usize) -> Result<WorkspaceConfig, ConfigError> {
+        let _lock = self.lock()?;
+        let config = parse_config(&fs::read(self.backup_path(index))?)?;
+        self.write(&config)?;
+        Ok(config)
+    }
+
     /// Returns the backing file path, primarily used in diagnostics.
     #[must_use]
     pub fn path(&self) -> &Path {
@@ -208,6 +680,18 @@ impl ConfigStore {
     }
 }
 
+fn parse_config(bytes: &[u8]) -> Result<WorkspaceConfig, ConfigError> {
+    let value = migrations::migrate(serde_json::from_slice(bytes)?)?;
+    Ok(serde_json::from_value(value)?)
+}
+
+/// Whether a parse failure looks like an interrupted write: the document
+/// ends early, or blocks that were allocated but never written read back as
+/// NUL bytes.
+fn is_truncated(bytes: &[u8], err: &serde_json::Error) -> bool {
+    err.is_eof() || bytes.contains(&0)
+}
+
 fn default_config_path() -> PathBuf {
     ProjectDirs::from(QUALIFIER, ORGANIZATION, APPLICATION)
         .map(|dirs| dirs.config_dir().join("workspace.json"))
@@ -308,12 +792,22 @@ impl<T: Clone + Send + 'static> CommandBus<T> {
 }
 
 /// Applies persisted configuration to the application at startup.
-pub fn bootstrap(app: &mut App, store: &ConfigStore) -> Result<WorkspaceConfig, ConfigError> {
-    let config = store.load()?;
-    if let Some(layout) = &config.layout_state {
+///
+/// Loads through [`ConfigStore::load_or_recover`], and falls back to defaults
+/// when even that fails, so configuration problems never prevent startup.
+/// What happened is recorded in the returned [`LoadedConfig`]; print its
+/// [`LoadedConfig::warnings`] or surface them in the UI.
+pub fn bootstrap(app: &mut App, store: &ConfigStore) -> LoadedConfig {
+    let loaded = store.load_or_recover().unwrap_or_else(|err| LoadedConfig {
+        config: WorkspaceConfig::default(),
+        restored_from: None,
+        unreadable: None,
+        error: Some(err),
+    });
+    if let Some(layout) = &loaded.config.layout_state {
         app.set_global(LayoutState(layout.clone()));
     }
-    Ok(config)
+    loaded
 }
 
 #[cfg(test)]
@@ -327,14 +821,195 @@ mod tests {
         let dir = tempdir().unwrap();
         let path = dir.path().join("config.json");
         let store = ConfigStore::new(path.clone());
-        let mut config = WorkspaceConfig::default();
-        config.window_state = Some("800x600".into());
+        let config = WorkspaceConfig {
+            window_state: Some("800x600".into()),
+            ..WorkspaceConfig::default()
+        };
         store.save(&config).unwrap();
         let loaded = store.load().unwrap();
         assert_eq!(loaded.window_state, Some("800x600".into()));
         assert_eq!(store.path(), path.as_path());
     }
 
+    #[test]
+    fn unversioned_configs_are_migrated() {
+        let dir = tempdir().unwrap();
+        let store = ConfigStore::new(dir.path().join("config.json"));
+        fs::write(
+            store.path(),
+            r#"{"window_state":null,"layout_state":"dock","recent_workspaces":["notes"]}"#,
+        )
+        .unwrap();
+
+        let loaded = store.load().unwrap();
+        assert_eq!(loaded.version, CONFIG_SCHEMA_VERSION);
+        assert_eq!(loaded.layout_state.as_deref(), Some("dock"));
+        assert_eq!(loaded.recent_workspaces, vec!["notes".to_string()]);
+    }
+
+    #[test]
+    fn newer_configs_are_left_in_place() {
+        let dir = tempdir().unwrap();
+        let store = ConfigStore::new(dir.path().join("config.json"));
+        fs::write(store.path(), r#"{"version":99}"#).unwrap();
+        assert!(matches!(
+            store.load(),
+            Err(ConfigError::UnsupportedVersion { found: 99, .. })
+        ));
+
+        let loaded = store.load_or_recover().unwrap();
+        assert_eq!(loaded.config, WorkspaceConfig::default());
+        assert_eq!(loaded.warnings().len(), 1);
+        assert!(loaded.unreadable.is_none());
+        assert!(matches!(
+            loaded.error,
+            Some(ConfigError::UnsupportedVersion { found: 99, .. })
+        ));
+        assert!(matches!(
+            store.save(&WorkspaceConfig::default()),
+            Err(ConfigError::UnsupportedVersion { .. })
+        ));
+        assert!(matches!(
+            store.update(|config| config.push_recent("notes")),
+            Err(ConfigError::UnsupportedVersion { .. })
+        ));
+        assert_eq!(
+            fs::read_to_string(store.path()).unwrap(),
+            r#"{"version":99}"#
+        );
+        assert!(store.backups().is_empty());
+    }
+
+    #[test]
+    fn unreadable_configs_are_moved_aside() {
+        let dir = tempdir().unwrap();
+        let store = ConfigStore::new(dir.path().join("config.json"));
+        fs::write(store.path(), "{ broken ]").unwrap();
+
+        let loaded = store.load_or_recover().unwrap();
+        assert_eq!(loaded.config, WorkspaceConfig::default());
+        assert_eq!(loaded.warnings().len(), 1);
+        let unreadable = loaded.unreadable.expect("file was moved aside");
+        assert_eq!(fs::read_to_string(&unreadable).unwrap(), "{ broken ]");
+        assert!(!store.path().exists());
+    }
+
+    #[test]
+    fn saves_rotate_backups() {
+        let dir = tempdir().unwrap();
+        let store = ConfigStore::new(dir.path().join("config.json")).with_backups(2);
+        for id in 0..4 {
+            let config = WorkspaceConfig {
+                window_state: Some(format!("save {id}")),
+                ..WorkspaceConfig::default()
+            };
+            store.save(&config).unwrap();
+        }
+
+        let backups = store.backups();
+        assert_eq!(backups.len(), 2);
+        assert!(backups[0].ends_with("config.json.bak.1"));
+        assert!(!dir.path().join("config.json.bak.3").exists());
+        let leftovers: Vec<_> = fs::read_dir(dir.path())
+            .unwrap()
+            .filter_map(Result::ok)
+            .filter(|entry| entry.file_name().to_string_lossy().contains(".tmp"))
+            .collect();
+        assert!(leftovers.is_empty());
+
+        let restored = store.restore_backup(2).unwrap();
+        assert_eq!(restored.window_state.as_deref(), Some("save 1"));
+        assert_eq!(store.load().unwrap(), restored);
+        assert_eq!(
+            store.restore_backup(1).unwrap().window_state.as_deref(),
+            Some("save 3")
+        );
+        assert!(matches!(store.restore_backup(5), Err(ConfigError::Io(_))));
+    }
+
+    #[test]
+    fn truncated_files_recover_from_the_newest_valid_backup() {
+        let dir = tempdir().unwrap();
+        let store = ConfigStore::new(dir.path().join("config.json"));
+        for state in ["older", "newer", "latest"] {
+            let config = WorkspaceConfig {
+                window_state: Some(state.into()),
+                ..WorkspaceConfig::default()
+            };
+            store.save(&config).unwrap();
+        }
+        fs::write(store.backup_path(1), "{\"version\":").unwrap();
+        let latest = fs::read(store.path()).unwrap();
+        fs::write(store.path(), &latest[..latest.len() / 2]).unwrap();
+
+        let loaded = store.load_or_recover().unwrap();
+        assert_eq!(loaded.config.window_state.as_deref(), Some("older"));
+        assert_eq!(loaded.restored_from, Some(store.backup_path(2)));
+        assert_eq!(store.load().unwrap(), loaded.config);
+
+        fs::write(store.path(), [0; 64]).unwrap();
+        assert_eq!(store.load().unwrap().window_state.as_deref(), Some("older"));
+    }
+
+    #[test]
+    fn updates_merge_with_other_writers() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("config.json");
+        let workbench = ConfigStore::new(path.clone());
+        let notes = ConfigStore::new(path.clone());
+
+        workbench
+            .update(|config| config.reduce_motion = true)
+            .unwrap();
+        let saved = notes
+            .update(|config| config.push_recent("notes.md"))
+            .unwrap();
+        assert!(saved.reduce_motion);
+        assert_eq!(saved.recent_workspaces, vec!["notes.md".to_string()]);
+
+        let changed = workbench.reload_if_changed().unwrap();
+        assert_eq!(changed.as_ref(), Some(&saved));
+        assert!(workbench.reload_if_changed().unwrap().is_none());
+        assert!(notes.reload_if_changed().unwrap().is_none());
+
+        let writers: Vec<_> = (0..8)
+            .map(|id| {
+                let store = ConfigStore::new(path.clone());
+                std::thread::spawn(move || {
+                    store
+                        .update(|config| config.window_state = Some(format!("{id}")))
+                        .unwrap();
+                    store
+                        .update(|config| config.push_recent(format!("workspace-{id}")))
+                        .unwrap();
+                })
+            })
+            .collect();
+        for writer in writers {
+            writer.join().unwrap();
+        }
+        let merged = workbench.load().unwrap();
+        assert_eq!(merged.recent_workspaces.len(), 9);
+        assert!(merged.reduce_motion);
+    }
+
+    #[test]
+    fn queued_updates_save_in_order() {
+        let dir = tempdir().unwrap();
+        let store = ConfigStore::new(dir.path().join("config.json"));
+        let clone = store.clone();
+
+        store.queue(|config| config.push_recent("first.md"));
+        clone.queue(|config| config.push_recent("second.md"));
+        let saved = clone.apply_queued().unwrap();
+        assert_eq!(
+            saved.recent_workspaces,
+            vec!["second.md".to_string(), "first.md".to_string()]
+        );
+        assert_eq!(store.apply_queued().unwrap(), saved);
+        assert_eq!(store.load().unwrap(), saved);
+    }
+
     #[test]
     fn localization_lookup() {
         let registry = LocalizationRegistry::new("en-US".parse().unwrap());
@@ -0,0 +1,80 @@
+//! Upgrades workspace configuration files written by older releases to the
+//! current schema.
+
+use serde::de::Error as _;
+use serde_json::{Map, Value};
+
+use crate::{ConfigError, CONFIG_SCHEMA_VERSION};
+
+/// Version assumed for files without a `version` field, which were written
+/// before the schema was versioned.
+const UNVERSIONED: u32 = 1;
+
+/// Upgrades a configuration object by exactly one version.
+type Migration = fn(&mut Map<String, Value>);
+
+/// Migration chain; entry `n` upgrades version `n + 1` to `n + 2`.
+///
+/// When a release changes the shape of [`crate::WorkspaceConfig`], bump
+/// [`CONFIG_SCHEMA_VERSION`] and append the step that rewrites the previous
+/// shape into the new one.
+const MIGRATIONS: &[Migration] = &[v1_to_v2];
+
+/// Version 2 only introduces the `version` field, which [`migrate`] stamps
+/// once the chain has run.
+fn v1_to_v2(_config: &mut Map<String, Value>) {}
+
+/// Runs every migration between the file's version and
+/// [`CONFIG_SCHEMA_VERSION`] and stamps the result with the current version.
+pub(crate) fn migrate(value: Value) -> Result<Value, ConfigError> {
+    let Value::Object(mut config) = value else {
+        return Err(serde_json::Error::custom("expected a JSON object").into());
+    };
+    let version = match config.get("version") {
+        None => UNVERSIONED,
+        Some(version) => version
+            .as_u64()
+            .and_then(|version| u32::try_from(version).ok())
+            .filter(|version| *version >= UNVERSIONED)
+            .ok_or_else(|| serde_json::Error::custom(format!("invalid version {version}")))?,
+    };
+    if version > CONFIG_SCHEMA_VERSION {
+        return Err(ConfigError::UnsupportedVersion {
+            found: version,
+            supported: CONFIG_SCHEMA_VERSION,
+        });
+    }
+
+    for migration in MIGRATIONS.iter().skip((version - UNVERSIONED) as usize) {
+        migration(&mut config);
+    }
+    config.insert("version".into(), CONFIG_SCHEMA_VERSION.into());
+    Ok(Value::Object(config))
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn chain_reaches_the_current_version() {
+        assert_eq!(
+            UNVERSIONED as usize + MIGRATIONS.len(),
+            CONFIG_SCHEMA_VERSION as usize
+        );
+    }
+
+    #[test]
+    fn rejects_malformed_versions() {
+        assert!(matches!(
+            migrate(json!({ "version": "two" })),
+            Err(ConfigError::Serialization(_))
+        ));
+        assert!(matches!(
+            migrate(json!([])),
+            Err(ConfigError::Serialization(_))
+        ));
+    }
+}
@@ -3,12 +3,26 @@ use std::{
     error::Error,
     fs,
     io::{self, Read},
-    path::PathBuf,
+    path::{Path, PathBuf},
     process::Command,
 };
 
 use clap::{Parser, Subcommand, ValueEnum};
+use designsystem::{
+    export_tokens, optimize_svg, IconLoader, SvgOptions, ThemeRegistry, ThemeVariant, TokenFormat,
+};
 use heck::ToKebabCase;
+use icon_sprites::{generate_icon_sprites, SpriteOptions};
+
+/// Root attributes outline icons get when the source omits them, so imported
+/// strokes match the rest of the set.
+const OUTLINE_DEFAULTS: &[(&str, &str)] = &[
+    ("fill", "none"),
+    ("stroke", "currentColor"),
+    ("stroke-width", "2"),
+    ("stroke-linecap", "round"),
+    ("stroke-linejoin", "round"),
+];
 
 fn main() {
     if let Err(err) = Xtask::parse().run() {
@@ -52,9 +66,13 @@ enum XtaskCommand {
         open: bool,
     },
     /// Normalize raw SVGs into the design system's icon pack.
+    #[command(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
     Icons {
+        #[command(subcommand)]
+        command: Option<IconsCommand>,
         /// Directory containing raw SVG assets.
-        input: PathBuf,
+        #[arg(required = true)]
+        input: Option<PathBuf>,
         /// Target pack that determines the output prefix.
         #[arg(long, value_enum, default_value_t = IconPack::Product)]
         pack: IconPack,
@@ -62,6 +80,49 @@ enum XtaskCommand {
         #[arg(long)]
         clean: bool,
     },
+    /// Work with the design system's theme tokens.
+    Tokens {
+        #[command(subcommand)]
+        command: TokensCommand,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+enum IconsCommand {
+    /// Rasterize every icon into PNGs and a sprite sheet with a JSON index.
+    Render {
+        /// Pixel size to render; repeat for several sizes.
+        #[arg(long = "size", default_values_t = [16, 24, 32])]
+        sizes: Vec<u32>,
+        /// Hex color substituted for `currentColor`; repeat for several.
+        #[arg(long = "color", default_values_t = [String::from("#000000")])]
+        colors: Vec<String>,
+        /// Transparent pixels around each frame in the sprite sheet.
+        #[arg(long, default_value_t = 1)]
+        padding: u32,
+        /// Directory that receives `png/`, `sprites.png` and `sprites.json`.
+        #[arg(long, default_value = "target/icon-sprites")]
+        out: PathBuf,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+enum TokensCommand {
+    /// Export a theme's tokens for web documentation and tooling.
+    Export {
+        /// Output format for the exported tokens.
+        #[arg(long, value_enum, default_value_t = TokenExportFormat::Css)]
+        format: TokenExportFormat,
+        /// Theme to export, either a built-in variant or a custom theme id.
+        #[arg(long, default_value = "light")]
+        theme: String,
+        /// Directory of custom JSON/TOML themes to register before exporting.
+        #[arg(long)]
+        theme_dir: Option<PathBuf>,
+        /// Write the export to this file instead of stdout.
+        #[arg(long)]
+        out: Option<PathBuf>,
+    },
 }
 
 #[derive(Clone, Copy, Debug, ValueEnum)]
@@ -104,6 +165,25 @@ enum IconPack {
     Product,
 }
 
+#[derive(Clone, Copy, Debug, ValueEnum)]
+enum TokenExportFormat {
+    Css,
+    Dtcg,
+    Figma,
+    Tailwind,
+}
+
+impl TokenExportFormat {
+    fn format(self) -> TokenFormat {
+        match self {
+            Self::Css => TokenFormat::Css,
+            Self::Dtcg => TokenFormat::Dtcg,
+            Self::Figma => TokenFormat::Figma,
+            Self::Tailwind => TokenFormat::Tailwind,
+        }
+    }
+}
+
 impl IconPack {
     fn prefix(self) -> &'static str {
         match self {
@@ -155,9 +235,35 @@ impl Xtask {
                 }
                 run(command)
             }
-            XtaskCommand::Icons { input, pack, clean } => {
+            XtaskCommand::Icons {
+                command:
+                    Some(IconsCommand::Render {
+                        sizes,
+                        colors,
+                        padding,
+                        out,
+                    }),
+                ..
+            } => Self::render_icons(sizes, colors, padding, &out),
+            XtaskCommand::Icons {
+                command: None,
+                input,
+                pack,
+                clean,
+            } => {
+                // Clap requires the input unless a subcommand is given.
+                let input = input.ok_or("missing icon input directory")?;
                 Self::import_icons(input, pack, clean)
             }
+            XtaskCommand::Tokens {
+                command:
+                    TokensCommand::Export {
+                        format,
+                        theme,
+                        theme_dir,
+                        out,
+                    },
+            } => Self::export_tokens(format, &theme, theme_dir, out),
         }
     }
 }
@@ -187,6 +293,61 @@ impl Xtask {
 
         fs::create_dir_all(&dest_dir)?;
 
+        // Validate the whole pack before touching the destination, so a
+        // malformed file never leaves a half-cleaned icon directory.
+        let mut icons = Vec::new();
+        let mut sidecars = Vec::new();
+        let mut errors = Vec::new();
+        for entry in fs::read_dir(&input_dir)? {
+            let entry = entry?;
+            let path = entry.path();
+            match path.extension().and_then(|ext| ext.to_str()) {
+                Some("svg") => {}
+                // Metadata sidecars are copied as-is; the build script
+                // validates them.
+                Some("toml") => {
+                    let stem = path
+                        .file_stem()
+                        .and_then(|stem| stem.to_str())
+                        .unwrap_or("icon");
+                    let filename = format!("{}-{}.toml", pack.prefix(), stem.to_kebab_case());
+                    sidecars.push((filename, path));
+                    continue;
+                }
+                _ => continue,
+            }
+            let mut svg = String::new();
+            fs::File::open(&path)?.read_to_string(&mut svg)?;
+            let stem = path
+                .file_stem()
+                .and_then(|stem| stem.to_str())
+                .unwrap_or("icon");
+            // `.filled`/`.duotone` style and `@16` size suffixes pass through
+            // untouched; only outline artwork gets the stroke defaults.
+            let (name, suffix) = stem.split_at(stem.find(['.', '@']).unwrap_or(stem.len()));
+            let options = if suffix.starts_with(".duotone") {
+                SvgOptions::MULTICOLOR
+            } else if suffix.starts_with(".filled") {
+                SvgOptions::MONOCHROME
+            } else {
+                SvgOptions {
+                    default_attributes: OUTLINE_DEFAULTS,
+                    ..SvgOptions::MONOCHROME
+                }
+            };
+            match optimize_svg(&svg, options) {
+                Ok(optimized) => {
+                    let filename =
+                        format!("{}-{}{suffix}.svg", pack.prefix(), name.to_kebab_case());
+                    icons.push((filename, optimized));
+                }
+                Err(err) => errors.push(format!("{}: {err}", path.display())),
+            }
+        }
+        if !errors.is_empty() {
+            return Err(errors.join("\n").into());
+        }
+
         if clean {
             for entry in fs::read_dir(&dest_dir)? {
                 let entry = entry?;
@@ -194,8 +355,7 @@ impl Xtask {
                     .path()
                     .file_name()
                     .and_then(|name| name.to_str())
-                    .map(|name| name.starts_with(pack.prefix()))
-                    .unwrap_or(false)
+                    .is_some_and(|name| name.starts_with(pack.prefix()))
                 {
                     fs::remove_file(entry.path())?;
                 }
@@ -203,29 +363,26 @@ impl Xtask {
         }
 
         let mut imported = 0usize;
-        for entry in fs::read_dir(&input_dir)? {
-            let entry = entry?;
-            let path = entry.path();
-            if path.extension().and_then(|ext| ext.to_str()) != Some("svg") {
-                continue;
-            }
-            let mut svg = String::new();
-            fs::File::open(&path)?.read_to_string(&mut svg)?;
-            let normalized = normalize_svg(&svg);
-            let stem = path
-                .file_stem()
-                .and_then(|stem| stem.to_str())
-                .unwrap_or("icon")
-                .to_kebab_case();
-            let filename = format!("{}-{}.svg", pack.prefix(), stem);
-            let dest_path = dest_dir.join(filename);
-            fs::write(&dest_path, normalized)?;
+        let mut saved = 0usize;
+        for (filename, optimized) in icons {
+            println!(
+                "  {filename}: {} -> {} bytes (saved {})",
+                optimized.original_bytes,
+                optimized.svg.len(),
+                optimized.saved_bytes()
+            );
+            saved += optimized.saved_bytes();
+            fs::write(dest_dir.join(filename), optimized.svg + "\n")?;
             imported += 1;
         }
+        for (filename, path) in &sidecars {
+            fs::copy(path, dest_dir.join(filename))?;
+        }
 
         println!(
-            "Imported {imported} {} icon(s) into {}",
+            "Imported {imported} {} icon(s) and {} metadata file(s) into {}, saving {saved} bytes",
             pack.prefix(),
+            sidecars.len(),
             dest_dir.display()
         );
 
@@ -233,6 +390,69 @@ impl Xtask {
     }
 }
 
+impl Xtask {
+    fn render_icons(
+        sizes: Vec<u32>,
+        colors: Vec<String>,
+        padding: u32,
+        out: &Path,
+    ) -> Result<(), Box<dyn Error>> {
+        let icons: Vec<(&str, &str)> = IconLoader::all()
+            .iter()
+            .map(|(stem, name)| (*stem, name.svg()))
+            .collect();
+        let options = SpriteOptions {
+            sizes,
+            colors,
+            padding,
+        };
+        let sheet = generate_icon_sprites(&icons, &options, out)?;
+        println!(
+            "Rendered {} bitmap(s) of {} icon(s) into {} ({}x{} sprite sheet)",
+            sheet.frames.len(),
+            icons.len(),
+            out.display(),
+            sheet.width,
+            sheet.height
+        );
+        Ok(())
+    }
+}
+
+impl Xtask {
+    fn export_tokens(
+        format: TokenExportFormat,
+        theme: &str,
+        theme_dir: Option<PathBuf>,
+        out: Option<PathBuf>,
+    ) -> Result<(), Box<dyn Error>> {
+        let registry = ThemeRegistry::new();
+        if let Some(dir) = theme_dir {
+            registry.load_dir(dir)?;
+        }
+        let variant = registry.variant_from_str(theme)?;
+        let definition = registry.definition(variant);
+
+        let selector = match variant {
+            ThemeVariant::Light => ":root".to_string(),
+            other => format!("[data-theme=\"{}\"]", other.as_str()),
+        };
+        let exported = export_tokens(&definition.tokens, format.format(), &selector);
+
+        match out {
+            Some(path) => {
+                if let Some(parent) = path.parent() {
+                    fs::create_dir_all(parent)?;
+                }
+                fs::write(&path, exported)?;
+                eprintln!("Exported {theme} tokens to {}", path.display());
+            }
+            None => print!("{exported}"),
+        }
+        Ok(())
+    }
+}
+
 impl DemoScenario {
     fn package_name(self) -> &'static str {
         match self {
@@ -278,26 +498,3 @@ fn run(mut command: Command) -> Result<(), Box<dyn Error>> {
         Err(format!("command exited with status {status}").into())
     }
 }
-
-fn normalize_svg(source: &str) -> String {
-    let mut svg = source.trim().to_string();
-    if !svg.contains("fill=\"none\"") {
-        svg = svg.replacen("<svg", "<svg fill=\"none\"", 1);
-    }
-    if !svg.contains("stroke=\"currentColor\"") {
-        svg = svg.replacen("<svg", "<svg stroke=\"currentColor\"", 1);
-    }
-    if !svg.contains("stroke-width=") {
-        svg = svg.replacen("<svg", "<svg stroke-width=\"2\"", 1);
-    }
-    if !svg.contains("stroke-linecap=") {
-        svg = svg.replacen("<svg", "<svg stroke-linecap=\"round\"", 1);
-    }
-    if !svg.contains("stroke-linejoin=") {
-        svg = svg.replacen("<svg", "<svg stroke-linejoin=\"round\"", 1);
-    }
-    if !svg.ends_with('\n') {
-        svg.push('\n');
-    }
-    svg
-}